@@ -0,0 +1,74 @@
+//! Draws the graphs shown in the README into `tests/`
+//!
+//! The random data comes from a fixed seed, so running this again only changes the images when the
+//! way charts are drawn changes.
+//!
+//! ```text
+//! cargo run --example readme_images
+//! ```
+
+use ferrischart::{
+    charts::{
+        histogram::Bins,
+        BarGraph, Histogram, LineGraph, PieChart, ScatterGraph
+    },
+    error::ChartResult
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn main() -> ChartResult<()> {
+    let mut rng = StdRng::seed_from_u64(2023);
+
+    let grades: Vec<(f64, f64)> = (0..101)
+        .map(|_| (rng.gen_range(1.0..9.0), rng.gen_range(1.0..7.0)))
+        .collect();
+    ScatterGraph::build()
+        .set_title("GCSE vs IB Grades")
+        .set_axis_text("GCSE Grades", "IB Grades")
+        .load_data(grades)
+        .draw("tests/scatter.png")?;
+
+    let (mut london, mut paris) = (vec![], vec![]);
+    for day in 0..10 {
+        london.push((day as f64, rng.gen_range(1.0..5.0)));
+        paris.push((day as f64, rng.gen_range(3.0..8.0)));
+    }
+    LineGraph::build()
+        .set_title("Daily Temperatures")
+        .set_axis_text("Day", "Temperature")
+        .add_series("London", london)
+        .add_series("Paris", paris)
+        .draw("tests/line.png")?;
+
+    BarGraph::build()
+        .set_title("Fruit Sales")
+        .set_axis_text("Fruit", "Sales")
+        .add_series("Monday", vec![("Apples", 5.0), ("Pears", 3.0), ("Plums", 7.0)])
+        .add_series("Tuesday", vec![("Apples", 4.0), ("Pears", 6.0), ("Kiwis", 2.0)])
+        .draw("tests/bar.png")?;
+
+    let totals: Vec<f64> = (0..500)
+        .map(|_| rng.gen_range(0.0..4.0) + rng.gen_range(0.0..4.0))
+        .collect();
+    Histogram::build()
+        .set_title("Sum of Two Dice")
+        .set_axis_text("Total", "Frequency")
+        .load_data(totals)
+        .set_bins(Bins::FreedmanDiaconis)
+        .draw("tests/histogram.png")?;
+
+    PieChart::build()
+        .set_title("Favourite Languages")
+        .load_data(vec![
+            ("Rust", 45.0),
+            ("Python", 25.0),
+            ("Go", 15.0),
+            ("C", 10.0),
+            ("Zig", 3.0),
+            ("Haskell", 2.0),
+        ])
+        .set_explode("Rust", 0.1)
+        .draw("tests/pie.png")?;
+
+    Ok(())
+}
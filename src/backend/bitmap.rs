@@ -0,0 +1,439 @@
+//! Rasterizes charts onto an image
+
+use image::{DynamicImage, ImageFormat, Rgb, Rgba, RgbaImage, RgbImage};
+use rusttype::{point, Scale};
+
+use crate::{error::ChartResult, font::FontFamily};
+use super::{dash_segments, Backend};
+
+/// Draws charts onto an [`RgbaImage`], which can be saved in any format the `image` crate supports.
+///
+/// Everything is anti-aliased, and translucent colours are blended with whatever was drawn underneath.
+/// Pixel `(x, y)` is the square centred on `(x, y)`, so lines along whole numbers cover whole pixels.
+pub struct BitmapBackend {
+    canvas: RgbaImage,
+    clip: Option<PixelArea>
+}
+
+impl BitmapBackend {
+    /// Creates a white canvas of the given size.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            canvas: RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255])),
+            clip: None
+        }
+    }
+
+    /// Creates a fully transparent canvas of the given size, so that charts with a translucent
+    /// background stay translucent.
+    pub fn transparent(width: u32, height: u32) -> Self {
+        Self {
+            canvas: RgbaImage::new(width, height),
+            clip: None
+        }
+    }
+
+    /// Finishes drawing and returns the canvas.
+    pub fn into_image(self) -> RgbaImage {
+        self.canvas
+    }
+
+    /// Finishes drawing and returns the canvas painted over white, which suits formats without
+    /// transparency like JPEG.
+    pub fn into_rgb_image(self) -> RgbImage {
+        RgbImage::from_fn(self.canvas.width(), self.canvas.height(), |x, y| {
+            let pixel = self.canvas.get_pixel(x, y);
+            let alpha = pixel[3] as f32 / 255.0;
+            Rgb([0, 1, 2].map(|channel| (pixel[channel] as f32 * alpha + 255.0 * (1.0 - alpha)).round() as u8))
+        })
+    }
+
+    /// Finishes drawing and returns the canvas ready to be encoded as `format`.
+    /// Formats that can hold transparency, such as PNG, keep the alpha channel, and the rest are painted over white.
+    pub fn into_encodable(self, format: ImageFormat) -> DynamicImage {
+        match format {
+            ImageFormat::Png | ImageFormat::Tiff | ImageFormat::Tga | ImageFormat::Ico => {
+                DynamicImage::ImageRgba8(self.canvas)
+            }
+            _ => DynamicImage::ImageRgb8(self.into_rgb_image()),
+        }
+    }
+
+    /// Saves the canvas, picking the image format from the path's extension.
+    pub fn save(self, path: &str) -> ChartResult<()> {
+        let format = ImageFormat::from_path(path)?;
+        self.into_encodable(format).save_with_format(path, format)?;
+        Ok(())
+    }
+
+    /// Blends `color` over a pixel, with `coverage` being how much of the pixel is covered from 0 to 1.
+    /// Pixels outside of the canvas or the clipping area are left alone.
+    fn blend(&mut self, x: i32, y: i32, color: Rgba<u8>, coverage: f32) {
+        let area = self.clip.unwrap_or(PixelArea::of(&self.canvas));
+        if !area.contains(x, y) || x < 0 || y < 0 || x >= self.canvas.width() as i32 || y >= self.canvas.height() as i32 {
+            return;
+        }
+
+        let alpha = color[3] as f32 / 255.0 * coverage.clamp(0.0, 1.0);
+        if alpha <= 0.0 {
+            return;
+        }
+
+        // the source-over operator, which is how paint covers whatever is underneath it
+        let pixel = self.canvas.get_pixel_mut(x as u32, y as u32);
+        let below = pixel[3] as f32 / 255.0;
+        let out_alpha = alpha + below * (1.0 - alpha);
+        for channel in 0..3 {
+            let value = (color[channel] as f32 * alpha + pixel[channel] as f32 * below * (1.0 - alpha)) / out_alpha;
+            pixel[channel] = value.round() as u8;
+        }
+        pixel[3] = (out_alpha * 255.0).round() as u8;
+    }
+
+    /// Blends `color` over every pixel `mask` covers.
+    fn fill_mask(&mut self, mask: &Mask, color: Rgba<u8>) {
+        for row in 0..mask.height {
+            for column in 0..mask.width {
+                let coverage = mask.coverage[row * mask.width + column];
+                if coverage > 0.0 {
+                    self.blend(mask.left + column as i32, mask.top + row as i32, color, coverage);
+                }
+            }
+        }
+    }
+
+    /// The coverage of every pixel of text, relative to its top-left corner, and the text's width.
+    /// Text is left out when there's no font to draw it in.
+    fn text_coverage(&self, text: &str, font: &FontFamily, size: f32) -> (Vec<(i32, i32, f32)>, i32) {
+        let Some(parsed) = font.font() else { return (vec![], 0) };
+        let scale = Scale::uniform(size);
+        let ascent = parsed.v_metrics(scale).ascent;
+        let (text_width, _) = self.text_size(text, font, size);
+
+        let mut pixels = vec![];
+        for glyph in parsed.layout(text, scale, point(0.0, ascent)) {
+            if let Some(bounds) = glyph.pixel_bounding_box() {
+                glyph.draw(|x, y, coverage| {
+                    pixels.push((bounds.min.x + x as i32, bounds.min.y + y as i32, coverage));
+                });
+            }
+        }
+
+        (pixels, text_width)
+    }
+}
+
+/// A rectangle of whole pixels, from `left` and `top` up to but not including `right` and `bottom`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PixelArea {
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32
+}
+
+impl PixelArea {
+    /// The whole of an image.
+    fn of(image: &RgbaImage) -> Self {
+        Self { left: 0, top: 0, right: image.width() as i32, bottom: image.height() as i32 }
+    }
+
+    /// Rounds a rectangle to whole pixels. Returns `None` if nothing would be drawn.
+    fn round(top_left: (f32, f32), size: (f32, f32)) -> Option<Self> {
+        let (left, top) = (top_left.0.round(), top_left.1.round());
+        let (right, bottom) = ((top_left.0 + size.0).round(), (top_left.1 + size.1).round());
+        if right <= left || bottom <= top {
+            return None;
+        }
+
+        Some(Self { left: left as i32, top: top as i32, right: right as i32, bottom: bottom as i32 })
+    }
+
+    /// The part of this area that's also inside `other`, which may be empty.
+    fn intersect(self, other: PixelArea) -> Self {
+        Self {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom)
+        }
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.left && x < self.right && y >= self.top && y < self.bottom
+    }
+}
+
+/// How many rows each row of pixels is split into when working out how much of each pixel a polygon covers.
+const SUBSAMPLES: usize = 4;
+
+/// How much of each pixel in an area is covered by a shape, from 0 to 1.
+/// Shapes added to the same mask are merged, so that where they overlap isn't blended twice.
+struct Mask {
+    left: i32,
+    top: i32,
+    width: usize,
+    height: usize,
+    coverage: Vec<f32>
+}
+
+impl Mask {
+    /// An empty mask big enough for shapes reaching as far as `points` do plus `padding` pixels,
+    /// cut down to the part of them inside `area` so that nothing is worked out for pixels that can't be drawn.
+    fn around(points: &[(f32, f32)], padding: f32, area: PixelArea) -> Self {
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::INFINITY, f32::INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for (x, y) in points.iter().filter(|(x, y)| x.is_finite() && y.is_finite()) {
+            min_x = min_x.min(*x);
+            min_y = min_y.min(*y);
+            max_x = max_x.max(*x);
+            max_y = max_y.max(*y);
+        }
+
+        // the casts saturate, so shapes reaching far off of the canvas are fine
+        let left = (((min_x - padding).floor() as i32).saturating_sub(1)).max(area.left);
+        let top = (((min_y - padding).floor() as i32).saturating_sub(1)).max(area.top);
+        let right = (((max_x + padding).ceil() as i32).saturating_add(2)).min(area.right);
+        let bottom = (((max_y + padding).ceil() as i32).saturating_add(2)).min(area.bottom);
+        if min_x > max_x || right <= left || bottom <= top {
+            return Self { left: 0, top: 0, width: 0, height: 0, coverage: vec![] };
+        }
+
+        let (width, height) = ((right - left) as usize, (bottom - top) as usize);
+        Self { left, top, width, height, coverage: vec![0.0; width * height] }
+    }
+
+    /// Merges how much of a pixel something covers into the mask.
+    fn cover(&mut self, x: i32, y: i32, coverage: f32) {
+        let (column, row) = (x - self.left, y - self.top);
+        if column < 0 || row < 0 || column >= self.width as i32 || row >= self.height as i32 {
+            return;
+        }
+
+        let index = row as usize * self.width + column as usize;
+        self.coverage[index] = self.coverage[index].max(coverage.min(1.0));
+    }
+
+    /// Adds a filled polygon to the mask, using the non-zero rule for parts that overlap themselves.
+    fn add_polygon(&mut self, points: &[(f32, f32)]) {
+        if points.len() < 3 || self.width == 0 {
+            return;
+        }
+
+        let (min_y, max_y) = points.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), (_, y)| (min.min(*y), max.max(*y)));
+        let first_row = ((min_y + 0.5).floor() as i32).max(self.top);
+        let last_row = ((max_y + 0.5).floor() as i32).min(self.top + self.height as i32 - 1);
+        let (left, right) = (self.left as f32 - 0.5, (self.left + self.width as i32) as f32 - 0.5);
+
+        let mut row_coverage = vec![0.0f32; self.width];
+        let mut crossings: Vec<(f32, i32)> = vec![];
+        for row in first_row..=last_row {
+            row_coverage.iter_mut().for_each(|coverage| *coverage = 0.0);
+
+            // each row of pixels is split into thinner rows, and every one of those adds its share of
+            // how far across each pixel the polygon reaches
+            for sample in 0..SUBSAMPLES {
+                let y = row as f32 - 0.5 + (sample as f32 + 0.5) / SUBSAMPLES as f32;
+                crossings.clear();
+                for (i, start) in points.iter().enumerate() {
+                    let end = points[(i + 1) % points.len()];
+                    if (start.1 <= y && end.1 > y) || (end.1 <= y && start.1 > y) {
+                        let x = start.0 + (y - start.1) * (end.0 - start.0) / (end.1 - start.1);
+                        crossings.push((x, if end.1 > start.1 { 1 } else { -1 }));
+                    }
+                }
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    if winding == 0 {
+                        continue;
+                    }
+
+                    let (start, end) = (pair[0].0.max(left), pair[1].0.min(right));
+                    let mut x = start;
+                    while x < end {
+                        // the part of the span inside of this pixel
+                        let pixel = (x + 0.5).floor();
+                        let pixel_end = (pixel + 0.5).min(end);
+                        let column = (pixel as i32 - self.left) as usize;
+                        if column < self.width {
+                            row_coverage[column] += (pixel_end - x) / SUBSAMPLES as f32;
+                        }
+                        x = pixel_end;
+                    }
+                }
+            }
+
+            for (column, coverage) in row_coverage.iter().enumerate() {
+                if *coverage > 0.0 {
+                    self.cover(self.left + column as i32, row, *coverage);
+                }
+            }
+        }
+    }
+
+    /// Adds a filled circle to the mask.
+    fn add_circle(&mut self, center: (f32, f32), radius: f32) {
+        let (top, bottom) = ((center.1 - radius - 1.0).floor() as i32, (center.1 + radius + 1.0).ceil() as i32);
+        let (left, right) = ((center.0 - radius - 1.0).floor() as i32, (center.0 + radius + 1.0).ceil() as i32);
+        let top = top.max(self.top);
+        let bottom = bottom.min(self.top + self.height as i32 - 1);
+        let left = left.max(self.left);
+        let right = right.min(self.left + self.width as i32 - 1);
+
+        for y in top..=bottom {
+            for x in left..=right {
+                // a pixel is covered by how far inside of the edge its centre is, fading out over a pixel
+                let distance = ((x as f32 - center.0).powi(2) + (y as f32 - center.1).powi(2)).sqrt();
+                let coverage = (radius + 0.5 - distance).clamp(0.0, 1.0);
+                if coverage > 0.0 {
+                    self.cover(x, y, coverage);
+                }
+            }
+        }
+    }
+}
+
+/// Lines are never drawn narrower than a pixel, since thinner lines would only show up as a faint blur.
+const MIN_LINE_WIDTH: f32 = 1.0;
+
+/// The four corners of a line `width` pixels wide, as a polygon.
+/// `cap` extends the line past both of its ends by that many pixels.
+fn thick_segment(start: (f32, f32), end: (f32, f32), width: f32, cap: f32) -> [(f32, f32); 4] {
+    // move both ends half of the width out to either side, at a right angle to the line
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = (dx * dx + dy * dy).sqrt().max(f32::EPSILON);
+    let (along_x, along_y) = (dx / length * cap, dy / length * cap);
+    let (offset_x, offset_y) = (-dy / length * width / 2.0, dx / length * width / 2.0);
+    let (start, end) = ((start.0 - along_x, start.1 - along_y), (end.0 + along_x, end.1 + along_y));
+
+    [
+        (start.0 + offset_x, start.1 + offset_y),
+        (end.0 + offset_x, end.1 + offset_y),
+        (end.0 - offset_x, end.1 - offset_y),
+        (start.0 - offset_x, start.1 - offset_y),
+    ]
+}
+
+impl Backend for BitmapBackend {
+    fn line(&mut self, start: (f32, f32), end: (f32, f32), width: f32, color: Rgba<u8>) {
+        // lines have square ends, so that lines meeting at a corner (like the axes) join up
+        self.polyline(&[start, end], width, color);
+    }
+
+    fn polyline(&mut self, points: &[(f32, f32)], width: f32, color: Rgba<u8>) {
+        let width = width.max(MIN_LINE_WIDTH);
+        let area = self.clip.unwrap_or(PixelArea::of(&self.canvas));
+        let mut mask = Mask::around(points, width, area);
+
+        // every segment is a polygon, with a circle rounding off every corner so that the segments join up
+        // open lines are stretched by half of the width at either end to square them off, whereas
+        // closed outlines (ending where they started) get a rounded corner there instead
+        let closed = points.len() > 2 && points.first() == points.last();
+        let last = points.len().saturating_sub(2);
+        for (i, segment) in points.windows(2).enumerate() {
+            let mut outline = thick_segment(segment[0], segment[1], width, 0.0);
+            let capped = thick_segment(segment[0], segment[1], width, if closed { 0.0 } else { width / 2.0 });
+            if i == 0 {
+                outline[0] = capped[0];
+                outline[3] = capped[3];
+            }
+            if i == last {
+                outline[1] = capped[1];
+                outline[2] = capped[2];
+            }
+            mask.add_polygon(&outline);
+        }
+        let corners = if closed { &points[..points.len() - 1] } else { &points[1..points.len().saturating_sub(1).max(1)] };
+        for point in corners {
+            mask.add_circle(*point, width / 2.0);
+        }
+
+        self.fill_mask(&mask, color);
+    }
+
+    fn dashed_line(&mut self, start: (f32, f32), end: (f32, f32), width: f32, color: Rgba<u8>, dashes: &[f32]) {
+        // dashes end exactly where the pattern says, rather than being squared off like lines are
+        let width = width.max(MIN_LINE_WIDTH);
+        let area = self.clip.unwrap_or(PixelArea::of(&self.canvas));
+        let mut mask = Mask::around(&[start, end], width, area);
+        for (dash_start, dash_end) in dash_segments(start, end, dashes) {
+            mask.add_polygon(&thick_segment(dash_start, dash_end, width, 0.0));
+        }
+
+        self.fill_mask(&mask, color);
+    }
+
+    fn circle(&mut self, center: (f32, f32), radius: f32, color: Rgba<u8>) {
+        let area = self.clip.unwrap_or(PixelArea::of(&self.canvas));
+        let mut mask = Mask::around(&[center], radius, area);
+        mask.add_circle(center, radius);
+        self.fill_mask(&mask, color);
+    }
+
+    fn fill_rect(&mut self, top_left: (f32, f32), size: (f32, f32), color: Rgba<u8>) {
+        // rectangles are kept to whole pixels, which keeps bars and backgrounds crisp
+        // only the part that can be drawn is visited, since bars off of the scale can reach billions of pixels away
+        let canvas = PixelArea::of(&self.canvas);
+        let visible = self.clip.unwrap_or(canvas).intersect(canvas);
+        if let Some(area) = PixelArea::round(top_left, size).map(|area| area.intersect(visible)) {
+            for y in area.top..area.bottom {
+                for x in area.left..area.right {
+                    self.blend(x, y, color, 1.0);
+                }
+            }
+        }
+    }
+
+    fn stroke_rect(&mut self, top_left: (f32, f32), size: (f32, f32), width: f32, color: Rgba<u8>) {
+        // outlines are four filled rectangles centred on the edges of the rectangle, with the sides
+        // fitting between the top and bottom so that the corners aren't blended twice
+        let width = width.max(MIN_LINE_WIDTH).round();
+        let (left, top) = ((top_left.0 - width / 2.0).round(), (top_left.1 - width / 2.0).round());
+        let (outer_width, outer_height) = ((size.0 + width).round(), (size.1 + width).round());
+        if outer_height <= width * 2.0 || outer_width <= width * 2.0 {
+            self.fill_rect((left, top), (outer_width, outer_height), color);
+            return;
+        }
+
+        self.fill_rect((left, top), (outer_width, width), color);
+        self.fill_rect((left, top + outer_height - width), (outer_width, width), color);
+        self.fill_rect((left, top + width), (width, outer_height - width * 2.0), color);
+        self.fill_rect((left + outer_width - width, top + width), (width, outer_height - width * 2.0), color);
+    }
+
+    fn polygon(&mut self, points: &[(f32, f32)], color: Rgba<u8>) {
+        let area = self.clip.unwrap_or(PixelArea::of(&self.canvas));
+        let mut mask = Mask::around(points, 0.0, area);
+        mask.add_polygon(points);
+        self.fill_mask(&mask, color);
+    }
+
+    fn text(&mut self, top_left: (f32, f32), text: &str, font: &FontFamily, size: f32, color: Rgba<u8>) {
+        let (pixels, _) = self.text_coverage(text, font, size);
+        let (left, top) = (top_left.0 as i32, top_left.1 as i32);
+        for (x, y, coverage) in pixels {
+            self.blend(left + x, top + y, color, coverage);
+        }
+    }
+
+    fn vertical_text(&mut self, top_left: (f32, f32), text: &str, font: &FontFamily, size: f32, color: Rgba<u8>) {
+        // the text is laid out as though it were horizontal, then every pixel is turned a
+        // quarter turn counter-clockwise, so what was its left edge ends up at the bottom
+        let (pixels, text_width) = self.text_coverage(text, font, size);
+        let (left, top) = (top_left.0 as i32, top_left.1 as i32);
+        for (x, y, coverage) in pixels {
+            self.blend(left + y, top + text_width - 1 - x, color, coverage);
+        }
+    }
+
+    fn clip(&mut self, top_left: (f32, f32), size: (f32, f32)) {
+        // an area too small to hold a single pixel still clips everything away, rather than nothing
+        self.clip = Some(PixelArea::round(top_left, size).unwrap_or(PixelArea { left: 0, top: 0, right: 0, bottom: 0 }));
+    }
+
+    fn reset_clip(&mut self) {
+        self.clip = None;
+    }
+}
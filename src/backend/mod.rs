@@ -0,0 +1,186 @@
+//! Backends that turn the primitives a chart is made of into an image format.
+//!
+//! Every chart draws itself as a series of primitives (lines, circles, rectangles, polygons and text)
+//! on a [`Backend`]. ferrischart ships with a [`BitmapBackend`] for raster images and an [`SvgBackend`]
+//! for SVG documents, and any other renderer can be plugged in by implementing [`Backend`] and passing
+//! it to a chart's `draw_to` method.
+//!
+//! ```rust no_run
+//! use ferrischart::{backend::SvgBackend, charts::ScatterGraph};
+//!
+//! let mut backend = SvgBackend::new(500, 500);
+//! ScatterGraph::build()
+//!     .set_title("GCSE vs IB Grades")
+//!     .load_data(vec![(4.0, 5.0), (7.0, 6.0)])
+//!     .draw_to(&mut backend)?;
+//! let document = backend.document();
+//! # Ok::<(), ferrischart::error::ChartErrors>(())
+//! ```
+
+mod bitmap;
+mod svg;
+
+pub use bitmap::BitmapBackend;
+pub use svg::SvgBackend;
+
+use std::{
+    io::{Seek, Write},
+    path::Path
+};
+
+use image::{ImageFormat, Rgba, RgbaImage};
+use imageproc::drawing;
+use rusttype::Scale;
+
+use crate::{error::ChartResult, font::FontFamily};
+
+/// The drawing primitives every chart is built from.
+///
+/// Positions are in pixels, with (0,0) being the top-left of the chart and the y-component increasing downwards.
+/// Widths and sizes are in pixels too, and are already scaled to the chart's size.
+/// Colours with an alpha below 255 are translucent, and should be blended with whatever is underneath them.
+pub trait Backend {
+    /// Draws a straight line between two points, `width` pixels wide.
+    fn line(&mut self, start: (f32, f32), end: (f32, f32), width: f32, color: Rgba<u8>);
+
+    /// Draws a line `width` pixels wide, connecting each point to the next.
+    fn polyline(&mut self, points: &[(f32, f32)], width: f32, color: Rgba<u8>);
+
+    /// Draws a filled circle.
+    fn circle(&mut self, center: (f32, f32), radius: f32, color: Rgba<u8>);
+
+    /// Draws a filled rectangle.
+    fn fill_rect(&mut self, top_left: (f32, f32), size: (f32, f32), color: Rgba<u8>);
+
+    /// Draws the outline of a rectangle, `width` pixels wide.
+    fn stroke_rect(&mut self, top_left: (f32, f32), size: (f32, f32), width: f32, color: Rgba<u8>);
+
+    /// Draws a filled polygon. The last point is implicitly connected back to the first.
+    fn polygon(&mut self, points: &[(f32, f32)], color: Rgba<u8>);
+
+    /// Draws text in `font` with its top-left corner at `top_left`. `size` is the height of the text in pixels.
+    fn text(&mut self, top_left: (f32, f32), text: &str, font: &FontFamily, size: f32, color: Rgba<u8>);
+
+    /// Draws text rotated a quarter turn counter-clockwise, so that it reads from bottom to top.
+    /// `top_left` is the top-left corner of the rotated text.
+    fn vertical_text(&mut self, top_left: (f32, f32), text: &str, font: &FontFamily, size: f32, color: Rgba<u8>);
+
+    /// Restricts everything drawn afterwards to a rectangle, until [`reset_clip`](Backend::reset_clip) is called.
+    /// Charts use this to keep data inside of their plotting area.
+    fn clip(&mut self, top_left: (f32, f32), size: (f32, f32));
+
+    /// Lets everything drawn afterwards cover the whole chart again.
+    fn reset_clip(&mut self);
+
+    /// Draws a dashed line, where `dashes` alternates between the lengths of dashes and the gaps between them.
+    /// An empty pattern draws a solid line.
+    ///
+    /// By default this draws every dash as its own [`line`](Backend::line), so backends that can draw dashes
+    /// natively may want to override it.
+    fn dashed_line(&mut self, start: (f32, f32), end: (f32, f32), width: f32, color: Rgba<u8>, dashes: &[f32]) {
+        for (dash_start, dash_end) in dash_segments(start, end, dashes) {
+            self.line(dash_start, dash_end, width, color);
+        }
+    }
+
+    /// The width and height text takes up when drawn unrotated.
+    /// Charts use this to lay out text, so backends drawing with a different font should override it.
+    ///
+    /// Without any font to measure with, text is estimated to be about half as wide as it is tall per character.
+    fn text_size(&self, text: &str, font: &FontFamily, size: f32) -> (i32, i32) {
+        match font.font() {
+            Some(parsed) => drawing::text_size(Scale::uniform(size), &parsed, text),
+            None => ((text.chars().count() as f32 * size * 0.55).round() as i32, size.round() as i32),
+        }
+    }
+}
+
+/// Splits a line into the dashes of a dash pattern, each as its start and end.
+/// Patterns without any length to them give back the whole line.
+pub(crate) fn dash_segments(start: (f32, f32), end: (f32, f32), dashes: &[f32]) -> Vec<((f32, f32), (f32, f32))> {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    let pattern_length: f32 = dashes.iter().map(|dash| dash.max(0.0)).sum();
+    if pattern_length <= 0.0 || !pattern_length.is_finite() || length == 0.0 {
+        return vec![(start, end)];
+    }
+
+    let point_at = |distance: f32| (start.0 + dx * distance / length, start.1 + dy * distance / length);
+    let mut segments = vec![];
+    let mut distance = 0.0;
+    // even entries of the pattern are dashes, odd entries are gaps
+    for (i, dash) in dashes.iter().map(|dash| dash.max(0.0)).cycle().enumerate() {
+        if distance >= length {
+            break;
+        }
+
+        let dash_end = (distance + dash).min(length);
+        if i % 2 == 0 && dash_end > distance {
+            segments.push((point_at(distance), point_at(dash_end)));
+        }
+        distance = dash_end;
+    }
+
+    segments
+}
+
+/// Draws a chart with the backend picked by the extension of `path`, and saves it there.
+/// Paths ending in `.svg` are written as SVG, everything else is rasterized.
+pub(crate) fn save(
+    path: &str,
+    width: u32,
+    height: u32,
+    plot: impl FnOnce(&mut dyn Backend) -> ChartResult<()>
+) -> ChartResult<()> {
+    let is_svg = Path::new(path)
+        .extension()
+        .map(|extension| extension.eq_ignore_ascii_case("svg"))
+        .unwrap_or(false);
+
+    if is_svg {
+        save_svg(path, width, height, plot)
+    } else {
+        // find out whether the format can be written before going to the trouble of drawing
+        ImageFormat::from_path(path)?;
+        let mut backend = BitmapBackend::transparent(width, height);
+        plot(&mut backend)?;
+        backend.save(path)
+    }
+}
+
+/// Draws a chart as SVG and saves it to `path`, whatever its extension.
+pub(crate) fn save_svg(
+    path: &str,
+    width: u32,
+    height: u32,
+    plot: impl FnOnce(&mut dyn Backend) -> ChartResult<()>
+) -> ChartResult<()> {
+    let mut backend = SvgBackend::new(width, height);
+    plot(&mut backend)?;
+    backend.save(path)
+}
+
+/// Draws a chart into an in-memory image.
+pub(crate) fn render(
+    width: u32,
+    height: u32,
+    plot: impl FnOnce(&mut dyn Backend) -> ChartResult<()>
+) -> ChartResult<RgbaImage> {
+    let mut backend = BitmapBackend::transparent(width, height);
+    plot(&mut backend)?;
+    Ok(backend.into_image())
+}
+
+/// Draws a chart and encodes it into `writer` in the given image format.
+pub(crate) fn write_to<W: Write + Seek>(
+    writer: &mut W,
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    plot: impl FnOnce(&mut dyn Backend) -> ChartResult<()>
+) -> ChartResult<()> {
+    let mut backend = BitmapBackend::transparent(width, height);
+    plot(&mut backend)?;
+    backend.into_encodable(format).write_to(writer, format)?;
+    Ok(())
+}
@@ -0,0 +1,256 @@
+//! Writes charts as SVG documents
+
+use std::fmt::Write;
+
+use image::Rgba;
+use rusttype::Scale;
+
+use crate::{error::ChartResult, font::FontFamily};
+use super::Backend;
+
+/// Draws charts as vector elements (`<line>`, `<circle>`, `<text>`, ...) of an SVG document.
+pub struct SvgBackend {
+    width: u32,
+    height: u32,
+    elements: String,
+    clip_count: usize,
+    clipping: bool
+}
+
+impl SvgBackend {
+    /// Creates an empty document of the given size. Charts paint their own background onto it.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            elements: String::new(),
+            clip_count: 0,
+            clipping: false
+        }
+    }
+
+    /// The finished SVG document.
+    pub fn document(&self) -> String {
+        // a clipping group that hasn't been reset yet still needs closing
+        let close_clip = if self.clipping { "</g>\n" } else { "" };
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n{2}{3}</svg>\n",
+            self.width,
+            self.height,
+            self.elements,
+            close_clip
+        )
+    }
+
+    /// Saves the document to a file path.
+    pub fn save(self, path: &str) -> ChartResult<()> {
+        std::fs::write(path, self.document())?;
+        Ok(())
+    }
+
+    /// The distance from the top of text to its baseline, which SVG positions text by.
+    /// Without a font to measure, it's assumed to be most of the text's height, as it is for most fonts.
+    fn ascent(&self, font: &FontFamily, size: f32) -> f32 {
+        match font.font() {
+            Some(parsed) => parsed.v_metrics(Scale::uniform(size)).ascent,
+            None => size * 0.8,
+        }
+    }
+
+    /// The attributes SVG needs to draw text in the same font and at the same size as it's measured.
+    /// rusttype scales fonts by their full height, whereas SVG scales them by their em square.
+    fn font_attributes(&self, font: &FontFamily, size: f32) -> String {
+        let font_size = match font.font() {
+            Some(parsed) => {
+                let v_metrics = parsed.v_metrics_unscaled();
+                size * parsed.units_per_em() as f32 / (v_metrics.ascent - v_metrics.descent)
+            }
+            None => size,
+        };
+
+        let mut attributes = format!(
+            "font-family=\"{}, sans-serif\" font-size=\"{}\"",
+            escape(font.name()),
+            number(font_size)
+        );
+        if font.weight() != 400 {
+            let _ = write!(attributes, " font-weight=\"{}\"", font.weight());
+        }
+
+        attributes
+    }
+}
+
+/// Rounds a number to 2 decimal places, so that documents stay small and diff cleanly.
+fn number(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Formats a colour as a `fill` or `stroke` attribute, along with its opacity if it's translucent.
+fn paint(attribute: &str, color: Rgba<u8>) -> String {
+    let [r, g, b, a] = color.0;
+    let mut paint = format!("{}=\"#{:02x}{:02x}{:02x}\"", attribute, r, g, b);
+    if a < 255 {
+        let _ = write!(paint, " {}-opacity=\"{}\"", attribute, number(a as f32 / 255.0));
+    }
+
+    paint
+}
+
+/// Formats points as a list of `x,y` pairs.
+fn points(points: &[(f32, f32)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{},{}", number(*x), number(*y)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Escapes characters that have a special meaning in XML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Backend for SvgBackend {
+    fn line(&mut self, start: (f32, f32), end: (f32, f32), width: f32, line_color: Rgba<u8>) {
+        let _ = writeln!(
+            self.elements,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {} stroke-width=\"{}\" stroke-linecap=\"square\"/>",
+            number(start.0),
+            number(start.1),
+            number(end.0),
+            number(end.1),
+            paint("stroke", line_color),
+            number(width)
+        );
+    }
+
+    fn polyline(&mut self, line_points: &[(f32, f32)], width: f32, line_color: Rgba<u8>) {
+        let _ = writeln!(
+            self.elements,
+            "<polyline points=\"{}\" fill=\"none\" {} stroke-width=\"{}\" stroke-linejoin=\"round\" stroke-linecap=\"square\"/>",
+            points(line_points),
+            paint("stroke", line_color),
+            number(width)
+        );
+    }
+
+    fn dashed_line(&mut self, start: (f32, f32), end: (f32, f32), width: f32, line_color: Rgba<u8>, dashes: &[f32]) {
+        if dashes.iter().all(|dash| *dash <= 0.0) {
+            self.line(start, end, width, line_color);
+            return;
+        }
+        let pattern: Vec<String> = dashes.iter().map(|dash| number(dash.max(0.0)).to_string()).collect();
+
+        let _ = writeln!(
+            self.elements,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {} stroke-width=\"{}\" stroke-dasharray=\"{}\"/>",
+            number(start.0),
+            number(start.1),
+            number(end.0),
+            number(end.1),
+            paint("stroke", line_color),
+            number(width),
+            pattern.join(" ")
+        );
+    }
+
+    fn circle(&mut self, center: (f32, f32), radius: f32, fill_color: Rgba<u8>) {
+        let _ = writeln!(
+            self.elements,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+            number(center.0),
+            number(center.1),
+            number(radius),
+            paint("fill", fill_color)
+        );
+    }
+
+    fn fill_rect(&mut self, top_left: (f32, f32), size: (f32, f32), fill_color: Rgba<u8>) {
+        let _ = writeln!(
+            self.elements,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+            number(top_left.0),
+            number(top_left.1),
+            number(size.0),
+            number(size.1),
+            paint("fill", fill_color)
+        );
+    }
+
+    fn stroke_rect(&mut self, top_left: (f32, f32), size: (f32, f32), width: f32, stroke_color: Rgba<u8>) {
+        let _ = writeln!(
+            self.elements,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" {} stroke-width=\"{}\"/>",
+            number(top_left.0),
+            number(top_left.1),
+            number(size.0),
+            number(size.1),
+            paint("stroke", stroke_color),
+            number(width)
+        );
+    }
+
+    fn polygon(&mut self, polygon_points: &[(f32, f32)], fill_color: Rgba<u8>) {
+        let _ = writeln!(
+            self.elements,
+            "<polygon points=\"{}\" {}/>",
+            points(polygon_points),
+            paint("fill", fill_color)
+        );
+    }
+
+    fn text(&mut self, top_left: (f32, f32), text: &str, font: &FontFamily, size: f32, text_color: Rgba<u8>) {
+        let _ = writeln!(
+            self.elements,
+            "<text x=\"{}\" y=\"{}\" {} {}>{}</text>",
+            number(top_left.0),
+            number(top_left.1 + self.ascent(font, size)),
+            self.font_attributes(font, size),
+            paint("fill", text_color),
+            escape(text)
+        );
+    }
+
+    fn vertical_text(&mut self, top_left: (f32, f32), text: &str, font: &FontFamily, size: f32, text_color: Rgba<u8>) {
+        // the text starts at the bottom of its rotated box, with its baseline
+        // the text's ascent away from the left of the box
+        let (text_width, _) = self.text_size(text, font, size);
+        let (x, y) = (number(top_left.0 + self.ascent(font, size)), number(top_left.1 + text_width as f32));
+        let _ = writeln!(
+            self.elements,
+            "<text x=\"{0}\" y=\"{1}\" transform=\"rotate(-90 {0} {1})\" {2} {3}>{4}</text>",
+            x,
+            y,
+            self.font_attributes(font, size),
+            paint("fill", text_color),
+            escape(text)
+        );
+    }
+
+    fn clip(&mut self, top_left: (f32, f32), size: (f32, f32)) {
+        // everything drawn until the clip is reset goes into a group clipped to the area
+        self.reset_clip();
+        self.clip_count += 1;
+        let _ = writeln!(
+            self.elements,
+            "<clipPath id=\"clip{0}\"><rect x=\"{1}\" y=\"{2}\" width=\"{3}\" height=\"{4}\"/></clipPath>\n<g clip-path=\"url(#clip{0})\">",
+            self.clip_count,
+            number(top_left.0),
+            number(top_left.1),
+            number(size.0),
+            number(size.1)
+        );
+        self.clipping = true;
+    }
+
+    fn reset_clip(&mut self) {
+        if self.clipping {
+            self.elements.push_str("</g>\n");
+            self.clipping = false;
+        }
+    }
+}
@@ -0,0 +1,629 @@
+//! Axis, tick and text drawing shared by the graphs that plot data against an x and y axis
+
+use image::Rgba;
+
+use crate::backend::Backend;
+use super::{
+    layout::Layout,
+    scale::{AxisScale, Scale},
+    ticks::{Formatter, Locator}
+};
+
+/// Lines drawn across the plotting area at every tick, to make values easier to read off.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GridLines {
+    #[cfg_attr(feature = "serde", serde(with = "super::theme::hex_color"))]
+    pub color: Rgba<u8>,
+    /// The width of each line, before the chart is scaled.
+    pub width: f32,
+    /// Alternating lengths of dashes and gaps, before the chart is scaled. Empty for solid lines.
+    pub dashes: Vec<f32>,
+}
+
+impl GridLines {
+    /// Solid grid lines.
+    pub fn new(color: Rgba<u8>, width: f32) -> Self {
+        Self { color, width, dashes: vec![] }
+    }
+
+    /// Dashes the grid lines, with alternating lengths of dashes and gaps such as `vec![4.0, 2.0]`.
+    pub fn dashed(mut self, dashes: Vec<f32>) -> Self {
+        self.dashes = dashes;
+
+        self
+    }
+}
+
+impl Default for GridLines {
+    /// Thin, light grey lines that stay out of the way of the data.
+    fn default() -> Self {
+        Self::new(Rgba([221, 221, 221, 255]), 1.0)
+    }
+}
+
+/// Which edges of the plotting area have a line (a spine) drawn along them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Spines {
+    pub left: bool,
+    pub bottom: bool,
+    pub top: bool,
+    pub right: bool,
+    /// How far every spine is moved outwards, away from the data, before the chart is scaled.
+    /// Ticks and labels move along with the left and bottom spines.
+    pub offset: f32,
+}
+
+impl Spines {
+    /// A spine along every edge, boxing the data in.
+    pub fn all() -> Self {
+        Self { left: true, bottom: true, top: true, right: true, offset: 0.0 }
+    }
+
+    /// No spines at all. Ticks and labels are still drawn.
+    pub fn none() -> Self {
+        Self { left: false, bottom: false, top: false, right: false, offset: 0.0 }
+    }
+
+    /// Moves every spine outwards by `offset`.
+    pub fn offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+
+        self
+    }
+}
+
+impl Default for Spines {
+    /// Spines along the left and bottom edges.
+    fn default() -> Self {
+        Self { left: true, bottom: true, top: false, right: false, offset: 0.0 }
+    }
+}
+
+/// Which way ticks point from their spine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TickDirection {
+    /// Away from the plotting area, towards the labels.
+    #[default]
+    Out,
+    /// Into the plotting area.
+    In,
+    /// Across the spine, both ways.
+    Both,
+}
+
+/// Adds the methods that name the axes and change how grid lines, spines and ticks are drawn to a chart's `impl` block.
+/// The chart needs `x_axis_text: &'a str` and `y_axis_text: &'a str` fields, and a `layout: Layout` field
+/// since the rest are all part of the chart's theme.
+macro_rules! axes_style_methods {
+    () => {
+        /// Set some text indicating what each respective axis represents.
+        pub fn set_axis_text(mut self, x_axis_text: &'a str, y_axis_text: &'a str) -> Self {
+            self.x_axis_text = x_axis_text;
+            self.y_axis_text = y_axis_text;
+
+            self
+        }
+
+        /// Draws lines across the plotting area at every major tick along the x-axis.
+        /// There are none in the default theme.
+        pub fn set_x_grid(mut self, grid: $crate::charts::axes::GridLines) -> Self {
+            self.layout.overrides.x_grid = Some(grid);
+
+            self
+        }
+
+        /// Draws lines across the plotting area at every major tick along the y-axis.
+        /// There are none in the default theme.
+        pub fn set_y_grid(mut self, grid: $crate::charts::axes::GridLines) -> Self {
+            self.layout.overrides.y_grid = Some(grid);
+
+            self
+        }
+
+        /// Draws lines across the plotting area at every minor tick along the x-axis.
+        /// There are none in the default theme.
+        pub fn set_x_minor_grid(mut self, grid: $crate::charts::axes::GridLines) -> Self {
+            self.layout.overrides.x_minor_grid = Some(grid);
+
+            self
+        }
+
+        /// Draws lines across the plotting area at every minor tick along the y-axis.
+        /// There are none in the default theme.
+        pub fn set_y_minor_grid(mut self, grid: $crate::charts::axes::GridLines) -> Self {
+            self.layout.overrides.y_minor_grid = Some(grid);
+
+            self
+        }
+
+        /// Sets which edges of the plotting area have a line along them.
+        /// The default theme has the left and bottom edges.
+        pub fn set_spines(mut self, spines: $crate::charts::axes::Spines) -> Self {
+            self.layout.overrides.spines = Some(spines);
+
+            self
+        }
+
+        /// Sets which way ticks point.
+        /// The default theme points them [`Out`](crate::charts::axes::TickDirection::Out).
+        pub fn set_tick_direction(mut self, direction: $crate::charts::axes::TickDirection) -> Self {
+            self.layout.overrides.tick_direction = Some(direction);
+
+            self
+        }
+
+        /// Sets the length of major ticks, before the chart is scaled. Minor ticks are half as long.
+        /// The default theme's are 5 long.
+        pub fn set_tick_length(mut self, length: f32) -> Self {
+            self.layout.overrides.tick_length = Some(length.max(0.0));
+
+            self
+        }
+    };
+}
+
+pub(crate) use axes_style_methods;
+
+/// How an axis spreads out, places and labels its ticks.
+/// The locator and formatter are picked by the scale, unless they've been set.
+#[derive(Default)]
+pub(crate) struct AxisOptions<'a> {
+    pub(crate) scale: AxisScale,
+    pub(crate) locator: Option<Box<dyn Locator + 'a>>,
+    pub(crate) formatter: Option<Box<dyn Formatter + 'a>>,
+}
+
+impl<'a> AxisOptions<'a> {
+    /// Works out the ticks for an axis that has to show every one of `values`.
+    /// Values the scale can't show, such as negative values on a log scale, are left out.
+    pub(crate) fn ticks(&self, values: impl IntoIterator<Item = f64>) -> AxisTicks {
+        let (min, max) = values
+            .into_iter()
+            .filter(|value| self.scale.can_show(*value))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+        let (min, max) = if min > max {
+            // nothing to show, so just show 1 to 10 (which works for every scale)
+            (1.0, 10.0)
+        } else {
+            (min, max)
+        };
+
+        let default_locator;
+        let locator = match &self.locator {
+            Some(locator) => locator.as_ref(),
+            None => {
+                default_locator = self.scale.locator();
+                default_locator.as_ref()
+            }
+        };
+        let major = locator.locate(min, max);
+        let labels = match &self.formatter {
+            Some(formatter) => formatter.format(&major),
+            None => self.scale.formatter().format(&major),
+        };
+        // minor ticks only go between the major ones
+        let (first, last) = (major.first().copied().unwrap_or(min), major.last().copied().unwrap_or(max));
+        let minor = locator
+            .minor(min, max)
+            .into_iter()
+            .filter(|tick| *tick > first && *tick < last && !major.contains(tick))
+            .collect();
+
+        AxisTicks {
+            scale: self.scale,
+            major,
+            minor,
+            labels,
+        }
+    }
+}
+
+/// Adds the methods that change how an x and y axis are scaled, located and formatted to a chart's `impl` block.
+/// The chart needs `x_axis: AxisOptions` and `y_axis: AxisOptions` fields.
+macro_rules! axis_methods {
+    () => {
+        /// Sets how values are spread along the x-axis. Defaults to [`AxisScale::Linear`](crate::charts::scale::AxisScale::Linear).
+        pub fn set_x_scale(mut self, scale: $crate::charts::scale::AxisScale) -> Self {
+            self.x_axis.scale = scale;
+
+            self
+        }
+
+        /// Sets how values are spread along the y-axis. Defaults to [`AxisScale::Linear`](crate::charts::scale::AxisScale::Linear).
+        pub fn set_y_scale(mut self, scale: $crate::charts::scale::AxisScale) -> Self {
+            self.y_axis.scale = scale;
+
+            self
+        }
+
+        /// Sets what picks the labels along the x-axis. Defaults to the one that suits the x-axis' scale.
+        pub fn set_x_locator<L: $crate::charts::ticks::Locator + 'a>(mut self, locator: L) -> Self {
+            self.x_axis.locator = Some(Box::new(locator));
+
+            self
+        }
+
+        /// Sets what picks the labels along the y-axis. Defaults to the one that suits the y-axis' scale.
+        pub fn set_y_locator<L: $crate::charts::ticks::Locator + 'a>(mut self, locator: L) -> Self {
+            self.y_axis.locator = Some(Box::new(locator));
+
+            self
+        }
+
+        /// Sets how the labels along the x-axis are written. Defaults to the one that suits the x-axis' scale.
+        pub fn set_x_formatter<F: $crate::charts::ticks::Formatter + 'a>(mut self, formatter: F) -> Self {
+            self.x_axis.formatter = Some(Box::new(formatter));
+
+            self
+        }
+
+        /// Sets how the labels along the y-axis are written. Defaults to the one that suits the y-axis' scale.
+        pub fn set_y_formatter<F: $crate::charts::ticks::Formatter + 'a>(mut self, formatter: F) -> Self {
+            self.y_axis.formatter = Some(Box::new(formatter));
+
+            self
+        }
+
+        /// Labels the x-axis with round dates and times in `timezone`, treating x values as seconds since the Unix epoch.
+        /// Needs the `chrono` feature. See [`time`](crate::charts::time).
+        #[cfg(feature = "chrono")]
+        pub fn set_x_time_axis<Tz>(mut self, timezone: Tz) -> Self
+        where
+            Tz: chrono::TimeZone + 'a,
+            Tz::Offset: std::fmt::Display,
+        {
+            self.x_axis.locator = Some(Box::new($crate::charts::time::TimeLocator::new(timezone.clone())));
+            self.x_axis.formatter = Some(Box::new($crate::charts::time::TimeFormatter::new(timezone)));
+
+            self
+        }
+
+        /// Labels the y-axis with round dates and times in `timezone`, treating y values as seconds since the Unix epoch.
+        /// Needs the `chrono` feature. See [`time`](crate::charts::time).
+        #[cfg(feature = "chrono")]
+        pub fn set_y_time_axis<Tz>(mut self, timezone: Tz) -> Self
+        where
+            Tz: chrono::TimeZone + 'a,
+            Tz::Offset: std::fmt::Display,
+        {
+            self.y_axis.locator = Some(Box::new($crate::charts::time::TimeLocator::new(timezone.clone())));
+            self.y_axis.formatter = Some(Box::new($crate::charts::time::TimeFormatter::new(timezone)));
+
+            self
+        }
+    };
+}
+
+pub(crate) use axis_methods;
+
+/// The ticks along a numeric axis, and the labels drawn next to them.
+pub(crate) struct AxisTicks {
+    scale: AxisScale,
+    major: Vec<f64>,
+    minor: Vec<f64>,
+    labels: Vec<String>,
+}
+
+/// What's labelled along an axis.
+pub(crate) enum AxisLabels<'a> {
+    /// Numeric ticks, placed by their values.
+    Values(&'a AxisTicks),
+    /// Named categories, each given an equal band of the axis.
+    /// A category's position along the axis is its index.
+    Categories(&'a [String]),
+}
+
+/// The scales worked out while drawing the axes.
+/// Used to place data onto the plotting area.
+pub(crate) struct Axes {
+    x_scale: Scale,
+    y_scale: Scale,
+    x_band_size: f32, // the pixels each x label gets to itself
+    y_band_size: f32, // the pixels each y label gets to itself
+    top_left: (f32, f32), // the top-left corner of the plotting area
+    size: (f32, f32), // the width and height of the plotting area
+}
+
+impl Axes {
+    /// Converts a data point into a pixel position on the canvas.
+    pub(crate) fn position(&self, (x, y): (f64, f64)) -> (f32, f32) {
+        (self.x_position(x), self.y_position(y))
+    }
+
+    /// Converts an x value into its pixel x-component on the canvas.
+    pub(crate) fn x_position(&self, x: f64) -> f32 {
+        self.x_scale.map(x)
+    }
+
+    /// Converts a y value into its pixel y-component on the canvas.
+    pub(crate) fn y_position(&self, y: f64) -> f32 {
+        self.y_scale.map(y)
+    }
+
+    /// The top-left corner and size of the area inside of the axes, where data is plotted.
+    pub(crate) fn plot_area(&self) -> ((f32, f32), (f32, f32)) {
+        (self.top_left, self.size)
+    }
+
+    /// Pulls a corner of a rectangle that's far off of the plotting area back to just outside of it.
+    /// Rectangles are clipped to the plotting area anyway, and ones billions of pixels across are too
+    /// big for their sizes to be worked out exactly.
+    pub(crate) fn clamp_corner(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        let ((left, top), (width, height)) = (self.top_left, self.size);
+        (x.clamp(left - width, left + width * 2.0), y.clamp(top - height, top + height * 2.0))
+    }
+
+    /// The start and end x-components of the pixels allocated to the x label at `index`.
+    pub(crate) fn x_band(&self, index: usize) -> (f32, f32) {
+        let center = self.x_scale.map(index as f64);
+        (center - self.x_band_size / 2.0, center + self.x_band_size / 2.0)
+    }
+
+    /// The bottom and top y-components of the pixels allocated to the y label at `index`.
+    /// Labels are counted upwards from the bottom of the y-axis.
+    pub(crate) fn y_band(&self, index: usize) -> (f32, f32) {
+        let center = self.y_scale.map(index as f64);
+        (center + self.y_band_size / 2.0, center - self.y_band_size / 2.0)
+    }
+}
+
+/// Works out the scale for an axis running from `start` to `end` (in pixels), along with the
+/// pixels each label gets to itself.
+/// Like a row of labels each given an equal slot, the first and last ticks sit half a slot in from
+/// the ends of the axis, so that neither is drawn right in the corner of the plotting area.
+fn axis_scale(kind: AxisScale, domain: Option<(f64, f64)>, count: usize, start: f32, end: f32) -> (Scale, f32) {
+    let length = end - start;
+    match domain {
+        Some(domain) if count > 1 => {
+            let inset = length / (2 * count) as f32;
+            (Scale::new(kind, domain, (start + inset, end - inset)), length.abs() / count as f32)
+        }
+        // a single label sits in the middle of the axis
+        Some(domain) => (Scale::new(kind, domain, (start, end)), length.abs()),
+        None => (Scale::new(kind, (1.0, 10.0), (start, end)), length.abs()),
+    }
+}
+
+/// The scale, labels, domain and number of labels along an axis.
+fn axis_labels(labels: &AxisLabels) -> (AxisScale, Vec<String>, Option<(f64, f64)>, usize) {
+    match labels {
+        AxisLabels::Values(ticks) => {
+            let domain = ticks.major.first().zip(ticks.major.last()).map(|(first, last)| (*first, *last));
+            (ticks.scale, ticks.labels.clone(), domain, ticks.major.len())
+        }
+        AxisLabels::Categories(categories) => {
+            let domain = (!categories.is_empty()).then(|| (0.0, (categories.len() - 1) as f64));
+            (AxisScale::Linear, categories.to_vec(), domain, categories.len())
+        }
+    }
+}
+
+/// Draws the axis lines, axis texts, labels and title around the plotting area of `layout`.
+pub(crate) fn draw_axes(
+    backend: &mut dyn Backend,
+    layout: &Layout,
+    title: &str,
+    (x_axis_text, y_axis_text): (&str, &str),
+    x_labels: AxisLabels,
+    y_labels: AxisLabels,
+) -> Axes {
+    let theme = &layout.theme;
+    let line_color = theme.axis_color;
+    let line_width = layout.line_width();
+    let (top_left, (plot_width, plot_height)) = layout.plot_area();
+    let canvas_height = layout.pixel_size().1 as f32;
+    let y_end = (top_left.0, top_left.1 + plot_height);
+
+    // write axis texts
+    let scale = layout.axis_text_size();
+
+    // write x-axis text
+    let text_color = theme.text_color;
+    // first step is to find the center of the x-axis where the text should be placed
+    // the text sits at the very bottom of the canvas
+    let (axis_x_text_size_x, axis_x_text_size_y) = backend.text_size(x_axis_text, &theme.axis_text_font, scale);
+    let x_axis_center = top_left.0 + (plot_width - axis_x_text_size_x as f32) / 2.0;
+    let x_axis_text_y = canvas_height - axis_x_text_size_y as f32;
+    backend.text((x_axis_center, x_axis_text_y), x_axis_text, &theme.axis_text_font, scale, text_color);
+
+    // write y-axis text
+    // this is rotated so that it reads from bottom to top, centered along the y-axis
+    let (axis_y_text_size_x, _) = backend.text_size(y_axis_text, &theme.axis_text_font, scale);
+    let y_axis_center = top_left.1 + (plot_height - axis_y_text_size_x as f32) / 2.0;
+    backend.vertical_text((0.0, y_axis_center), y_axis_text, &theme.axis_text_font, scale, text_color);
+
+    // now we work out how values map onto each axis
+    // the y-axis runs from the bottom of the plotting area to the top, since bigger values are drawn higher up
+    let (x_kind, x_strings, x_domain, x_count) = axis_labels(&x_labels);
+    let (y_kind, y_strings, y_domain, y_count) = axis_labels(&y_labels);
+    let (x_scale, x_band_size) = axis_scale(x_kind, x_domain, x_count, top_left.0, top_left.0 + plot_width);
+    let (y_scale, y_band_size) = axis_scale(y_kind, y_domain, y_count, y_end.1, top_left.1);
+    // categories are placed by their index, numbers by their value
+    let tick_value = |labels: &AxisLabels, index: usize| match labels {
+        AxisLabels::Values(ticks) => ticks.major[index],
+        AxisLabels::Categories(_) => index as f64,
+    };
+    let minor_ticks = |labels: &AxisLabels| match labels {
+        AxisLabels::Values(ticks) => ticks.minor.clone(),
+        AxisLabels::Categories(_) => vec![],
+    };
+
+    // the plotting area's background and grid lines go first, so that everything else is drawn over them
+    if let Some(plot_background) = theme.plot_background {
+        backend.fill_rect(top_left, (plot_width, plot_height), plot_background);
+    }
+    let x_grid_lines = [
+        (&theme.x_grid, (0..x_count).map(|i| tick_value(&x_labels, i)).collect::<Vec<_>>()),
+        (&theme.x_minor_grid, minor_ticks(&x_labels)),
+    ];
+    for (grid, values) in x_grid_lines {
+        if let Some(grid) = grid {
+            for value in values {
+                let x = x_scale.map(value);
+                draw_grid_line(backend, layout, grid, (x, top_left.1), (x, y_end.1));
+            }
+        }
+    }
+    let y_grid_lines = [
+        (&theme.y_grid, (0..y_count).map(|i| tick_value(&y_labels, i)).collect::<Vec<_>>()),
+        (&theme.y_minor_grid, minor_ticks(&y_labels)),
+    ];
+    for (grid, values) in y_grid_lines {
+        if let Some(grid) = grid {
+            for value in values {
+                let y = y_scale.map(value);
+                draw_grid_line(backend, layout, grid, (top_left.0, y), (top_left.0 + plot_width, y));
+            }
+        }
+    }
+
+    // now we need to draw the spines (the axis lines)
+    // they run along the edges of the plotting area, which sits inside of the margins,
+    // and can be pushed outwards away from the data
+    let spines = theme.spines;
+    let offset = layout.scaled(spines.offset);
+    let (left, right) = (top_left.0 - offset, top_left.0 + plot_width + offset);
+    let (top, bottom) = (top_left.1 - offset, y_end.1 + offset);
+    // spines that are pushed out still only run as far as the plotting area does
+    let (span_left, span_right) = (top_left.0, top_left.0 + plot_width);
+    let (span_top, span_bottom) = (top_left.1, y_end.1);
+    if spines.left {
+        backend.line((left, span_top), (left, span_bottom), line_width, line_color);
+    }
+    if spines.bottom {
+        backend.line((span_left, bottom), (span_right, bottom), line_width, line_color);
+    }
+    if spines.top {
+        backend.line((span_left, top), (span_right, top), line_width, line_color);
+    }
+    if spines.right {
+        backend.line((right, span_top), (right, span_bottom), line_width, line_color);
+    }
+
+    // draw y-labels
+    let label_scale = layout.label_size();
+    // ticks reach into the plotting area, out of it, or both, from the spine
+    let tick_size = layout.scaled(theme.tick_length);
+    let (tick_in, tick_out) = match theme.tick_direction {
+        TickDirection::Out => (0.0, tick_size),
+        TickDirection::In => (tick_size, 0.0),
+        TickDirection::Both => (tick_size, tick_size),
+    };
+    // labels start where the ticks end, with a small gap when the ticks don't point towards them
+    let label_gap = tick_out.max(layout.scaled(theme.label_padding));
+
+    // lets iterate through the y labels and draw them on now
+    for (i, label_string) in y_strings.iter().enumerate() {
+        // first we'll draw a line indicating the real position of the number
+        // tick size - 5, scaled along with the rest of the chart
+        /*
+        1) The y-component of the tick is wherever the y scale maps the label's value to
+        2) The tick crosses the left spine, so its x-component is the same as the spine's
+        3) The x position needs to change on the y-axis however when drawing the tick, since the tick
+        extends on the x-axis, so we subtract how far it points outwards from the spine: left - tick_out
+        */
+        let tick_y = y_scale.map(tick_value(&y_labels, i));
+        let tick_start = (left + tick_in, tick_y);
+        let tick_end = (left - tick_out, tick_y);
+        if tick_size > 0.0 {
+            backend.line(tick_start, tick_end, line_width, line_color);
+        }
+
+        /*
+        Drawing on the text will be slightly different.
+        1) First step is to calculate the position where the tick ends, so we can make sure that
+        our text begins there (that's already been done above)
+        2) Since the co-ordinate system considers (0,0) as the top-left, the text's top-left pixel will be set
+        as the point we calculate
+        3) We want the middle of our label to be aligned with the tick, so we need to perform the following:
+            - calculate the text's height (since we're dealing with the y-axis here)
+            - divide this height by 2, to calculate a rough center
+            - offset the y-component of the text by this value
+        4) Running through step 3 makes sure that the center of the label is actually aligned with the tick,
+        however, it'll still be overlapping. To fix this, we'll decrease its x-component by the text-width
+        */
+        let (text_width, text_height) = backend.text_size(label_string, &theme.label_font, label_scale);
+        let rough_center = text_height / 2;
+        let (text_location_x, text_location_y) = ((left - label_gap - text_width as f32), (tick_y - rough_center as f32));
+
+        backend.text((text_location_x, text_location_y), label_string, &theme.label_font, label_scale, text_color);
+    }
+
+    // minor ticks are half the size, and don't have a label
+    for minor in minor_ticks(&y_labels).into_iter().filter(|_| tick_size > 0.0) {
+        let tick_y = y_scale.map(minor);
+        backend.line((left + tick_in / 2.0, tick_y), (left - tick_out / 2.0, tick_y), line_width, line_color);
+    }
+
+    // now let's iterate through the x labels and do the same
+    for (i, label_string) in x_strings.iter().enumerate() {
+        // again first, we need to draw on a tick
+        /*
+        1) Same as before, the x scale tells us where along the x-axis the label's value is
+        2) This time, our y-component will stay the same, since we're only dealing with the x-axis here,
+        and the ticks y-component (at least the position where it starts) is the x-axis line. Only time we change
+        its y-component is when we alter the position so we can draw the tick
+        */
+        let tick_x = x_scale.map(tick_value(&x_labels, i));
+        let tick_start = (tick_x, bottom - tick_in);
+        let tick_end = (tick_x, bottom + tick_out); // increment y-value
+        // by tick_out since that's how far our tick reaches below the spine
+        if tick_size > 0.0 {
+            backend.line(tick_start, tick_end, line_width, line_color);
+        }
+
+        /*
+        Drawing text on is slightly different from how we drew on our y-axis text
+        1) First step (similarly to before) is to place our text's top-left pixel at the position where the
+        tick ends
+        2) However, this makes it so that the text renders just to the right of the tick
+        3) If we want to make the text render directly in the middle of it, we need to have half of the pixels
+        to the left of the tick, and half of the pixels to the right of the tick.
+        4) An easy way to do this is to calculate the text width, and divide by 2 to get an offset value
+        5) Offset its x-component by decreasing it by this offset value
+        */
+        let (text_width, _) = backend.text_size(label_string, &theme.label_font, label_scale);
+        let offset_value = text_width / 2;
+        let (text_location_x, text_location_y) = (tick_x, bottom + label_gap);
+        let text_location_x = text_location_x - offset_value as f32;
+        backend.text((text_location_x, text_location_y), label_string, &theme.label_font, label_scale, text_color);
+    }
+
+    for minor in minor_ticks(&x_labels).into_iter().filter(|_| tick_size > 0.0) {
+        let tick_x = x_scale.map(minor);
+        backend.line((tick_x, bottom - tick_in / 2.0), (tick_x, bottom + tick_out / 2.0), line_width, line_color);
+    }
+
+    // now that all of the important sections are complete, we can now
+    // focus on drawing a title
+    // the title will be at the same y position as where the y-axis line starts
+    // we want the bottom of the title to be flush with the top of the y-axis line
+    // so we just offset by the text's height
+
+    let title_scale = layout.title_size();
+    let (title_width, title_height) = backend.text_size(title, &theme.title_font, title_scale);
+    let center = top_left.0 + (plot_width - title_width as f32) / 2.0;
+    let (title_pos_x, title_pos_y) = (center, top_left.1 - title_height as f32);
+    backend.text((title_pos_x, title_pos_y), title, &theme.title_font, title_scale, text_color);
+
+    Axes {
+        x_scale,
+        y_scale,
+        x_band_size,
+        y_band_size,
+        top_left,
+        size: (plot_width, plot_height),
+    }
+}
+
+/// Draws a single grid line, dashed if the grid lines are.
+fn draw_grid_line(backend: &mut dyn Backend, layout: &Layout, grid: &GridLines, start: (f32, f32), end: (f32, f32)) {
+    let dashes: Vec<f32> = grid.dashes.iter().map(|length| layout.scaled(*length)).collect();
+    backend.dashed_line(start, end, layout.scaled(grid.width), grid.color, &dashes);
+}
@@ -0,0 +1,308 @@
+//! Bar Graph implementation
+
+use image::Rgba;
+
+use crate::{
+    backend::Backend,
+    data::{missing_values_methods, MissingValues, Numeric},
+    error::{ChartErrors, ChartResult}
+};
+use super::{
+    layout::{layout_methods, Layout},
+    axes::{axes_style_methods, draw_axes, AxisLabels, AxisOptions},
+    category::{category_methods, Categories},
+    legend::{draw_legend, legend_methods, trace_area, LegendPosition, Swatch},
+    scale::AxisScale,
+    ticks::{Formatter, Locator}
+};
+
+/// The direction bars extend in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Categories run along the x-axis and bars grow upwards.
+    #[default]
+    Vertical,
+    /// Categories run along the y-axis and bars grow to the right.
+    Horizontal,
+}
+
+/// A named set of values, one for each category of the graph.
+struct Series<'a> {
+    name: &'a str,
+    values: Vec<f64>
+}
+
+/// A struct that aids in building bar graphs.
+pub struct BarGraph<'a> {
+    title: &'a str,
+    x_axis_text: &'a str,
+    y_axis_text: &'a str,
+    categories: Categories,
+    value_axis: AxisOptions<'a>,
+    series: Vec<Series<'a>>,
+    orientation: Orientation,
+    stacked: bool,
+    legend_position: LegendPosition,
+    missing_values: MissingValues,
+    layout: Layout
+}
+
+impl<'a> Default for BarGraph<'a> {
+    fn default() -> Self {
+        Self {
+            title: "unset",
+            x_axis_text: "unset",
+            y_axis_text: "unset",
+            categories: Categories::default(),
+            value_axis: AxisOptions::default(),
+            series: vec![],
+            orientation: Orientation::Vertical,
+            stacked: false,
+            legend_position: LegendPosition::default(),
+            missing_values: MissingValues::Skip,
+            layout: Layout::default()
+        }
+    }
+}
+
+impl<'a> BarGraph<'a> {
+    /// Begin the process of building a bar graph.
+    /// Initially returns the default value.
+    pub fn build() -> Self {
+        Self::default()
+    }
+
+    /// Loads `(category, value)` pairs as a single, unnamed series.
+    /// This replaces any series previously added.
+    pub fn load_data<I, S, T>(mut self, data: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: Into<String>,
+        T: Numeric,
+    {
+        self.categories.clear();
+        self.series.clear();
+        self.add_series("", data)
+    }
+
+    /// Adds a named series of `(category, value)` pairs to the graph.
+    /// Categories are drawn in the order they are first seen unless given a
+    /// [`CategoryOrder`](crate::charts::category::CategoryOrder), and a series that doesn't have a value
+    /// for a category is treated as having 0 there.
+    /// Series with a non-empty name are listed in the graph's legend.
+    pub fn add_series<I, S, T>(mut self, name: &'a str, data: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: Into<String>,
+        T: Numeric,
+    {
+        let mut values = vec![0.0; self.categories.len()];
+        for (category, value) in data {
+            let index = self.categories.index(category.into());
+            if index == values.len() {
+                // a category we haven't seen before, every other series gets a 0 for it
+                for series in &mut self.series {
+                    series.values.push(0.0);
+                }
+                values.push(0.0);
+            }
+            values[index] = value.to_f64();
+        }
+
+        self.series.push(Series { name, values });
+
+        self
+    }
+
+    /// Sets whether bars grow upwards ([`Orientation::Vertical`]) or to the right ([`Orientation::Horizontal`]).
+    pub fn set_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+
+        self
+    }
+
+    /// Sets whether the series of each category are stacked on top of each other
+    /// instead of being drawn side by side.
+    pub fn set_stacked(mut self, stacked: bool) -> Self {
+        self.stacked = stacked;
+
+        self
+    }
+
+    /// Sets what picks the labels along the value axis, which is the y-axis of vertical graphs
+    /// and the x-axis of horizontal ones. Defaults to the one that suits the value axis' scale.
+    pub fn set_value_locator<L: Locator + 'a>(mut self, locator: L) -> Self {
+        self.value_axis.locator = Some(Box::new(locator));
+
+        self
+    }
+
+    /// Sets how values are spread along the value axis. Defaults to [`AxisScale::Linear`].
+    /// A log scale can't show 0, so bars start from the edge of the plotting area instead, and bars
+    /// for values it can't show at all are left out.
+    pub fn set_value_scale(mut self, scale: AxisScale) -> Self {
+        self.value_axis.scale = scale;
+
+        self
+    }
+
+    /// Sets how the labels along the value axis are written. Defaults to the one that suits the value axis' scale.
+    pub fn set_value_formatter<F: Formatter + 'a>(mut self, formatter: F) -> Self {
+        self.value_axis.formatter = Some(Box::new(formatter));
+
+        self
+    }
+
+    /// How many of the loaded points won't be drawn. See [`MissingValues`] for what's counted.
+    pub fn dropped_points(&self) -> usize {
+        self.series
+            .iter()
+            .flat_map(|series| series.values.iter())
+            .filter(|value| !self.value_axis.scale.can_show(**value))
+            .count()
+    }
+
+    /// The values the value axis has to be able to show, including the 0 every bar starts from.
+    fn value_extent(&self) -> Vec<f64> {
+        let mut extent = vec![0.0];
+        if self.stacked {
+            for i in 0..self.categories.len() {
+                let values = self.series.iter().map(|s| s.values[i]).filter(|v| v.is_finite());
+                // folding from 0.0 rather than using sum, which starts from -0.0
+                extent.push(values.clone().filter(|v| *v > 0.0).fold(0.0, |a, b| a + b));
+                extent.push(values.filter(|v| *v < 0.0).fold(0.0, |a, b| a + b));
+            }
+        } else {
+            extent.extend(self.series.iter().flat_map(|s| s.values.iter().copied()).filter(|v| v.is_finite()));
+        }
+
+        extent
+    }
+
+    category_methods!();
+
+    legend_methods!();
+
+    axes_style_methods!();
+
+    missing_values_methods!();
+
+    layout_methods!();
+
+    /// Draws the graph with any [`Backend`], including ones implemented outside of ferrischart.
+    /// Fails with [`ChartErrors::EmptyData`] if there are no categories, and with
+    /// [`ChartErrors::NonFiniteValue`] if a bar's value is missing and [`MissingValues::Error`] is set.
+    pub fn draw_to(mut self, backend: &mut dyn Backend) -> ChartResult<()> {
+        self.layout.apply_overrides();
+        if self.categories.is_empty() {
+            return Err(ChartErrors::EmptyData);
+        }
+        for series in &self.series {
+            self.missing_values.check(series.name, series.values.iter().map(|value| value.is_finite()))?;
+        }
+        self.layout.draw_background(backend);
+
+        // categories go on one axis, values on the other
+        // horizontal graphs list their categories from the top down, so the
+        // labels are reversed since the y-axis is drawn from the bottom up
+        // stacking changes how tall the tallest bar can be, so the value labels are only picked now
+        let value_labels = self.value_axis.ticks(self.value_extent());
+        let order = self.categories.ordered();
+        let mut category_labels = self.categories.ordered_names();
+        let (x_labels, y_labels) = match self.orientation {
+            Orientation::Vertical => (AxisLabels::Categories(&category_labels), AxisLabels::Values(&value_labels)),
+            Orientation::Horizontal => {
+                category_labels.reverse();
+                (AxisLabels::Values(&value_labels), AxisLabels::Categories(&category_labels))
+            }
+        };
+        let axes = draw_axes(
+            backend,
+            &self.layout,
+            self.title,
+            (self.x_axis_text, self.y_axis_text),
+            x_labels,
+            y_labels
+        );
+
+        // works out the pixels allocated to a category along the category axis
+        let category_count = self.categories.len();
+        let band = |index: usize| match self.orientation {
+            Orientation::Vertical => axes.x_band(index),
+            Orientation::Horizontal => axes.y_band(category_count - 1 - index),
+        };
+        // works out where a value sits along the value axis
+        // a log scale never reaches 0, so bars start from the edge of the plotting area instead
+        let (top_left, size) = axes.plot_area();
+        let scale = self.value_axis.scale;
+        let value_position = |value: f64| match self.orientation {
+            Orientation::Vertical if value == 0.0 && !scale.can_show(value) => top_left.1 + size.1,
+            Orientation::Horizontal if value == 0.0 && !scale.can_show(value) => top_left.0,
+            Orientation::Vertical => axes.y_position(value),
+            Orientation::Horizontal => axes.x_position(value),
+        };
+
+        // leave 10% of each category's pixels empty on either side so that neighbouring groups don't touch
+        let series_count = self.series.len();
+        let colors: Vec<Rgba<u8>> = (0..self.series.len()).map(|i| self.layout.theme.palette.color(i)).collect();
+        let mut plotted = vec![];
+        backend.clip(top_left, size);
+        for (position, category) in order.into_iter().enumerate() {
+            let (band_start, band_end) = band(position);
+            let band_size = band_end - band_start;
+            let group_start = band_start + band_size * 0.1;
+            let group_size = band_size * 0.8;
+
+            let mut positive_total = 0.0;
+            let mut negative_total = 0.0;
+            for (i, series) in self.series.iter().enumerate() {
+                let value = series.values[category];
+                if !scale.can_show(value) {
+                    continue;
+                }
+
+                // stacked bars share the whole group and start where the previous series ended,
+                // grouped bars split the group between every series and start at 0
+                let ((cross_start, cross_end), (value_start, value_end)) = if self.stacked {
+                    let total = if value >= 0.0 { &mut positive_total } else { &mut negative_total };
+                    let start = *total;
+                    *total += value;
+                    ((group_start, group_start + group_size), (start, *total))
+                } else {
+                    let bar_size = group_size / series_count as f32;
+                    let bar_start = group_start + bar_size * i as f32;
+                    ((bar_start, bar_start + bar_size), (0.0, value))
+                };
+
+                let (value_start, value_end) = (value_position(value_start), value_position(value_end));
+                let (corner_a, corner_b) = match self.orientation {
+                    Orientation::Vertical => ((cross_start, value_start), (cross_end, value_end)),
+                    Orientation::Horizontal => ((value_start, cross_start), (value_end, cross_end)),
+                };
+                let (corner_a, corner_b) = (axes.clamp_corner(corner_a), axes.clamp_corner(corner_b));
+                fill_bar(backend, corner_a, corner_b, colors[i]);
+                plotted.extend(trace_area(corner_a, corner_b, self.layout.scaled(5.0)));
+            }
+        }
+
+        backend.reset_clip();
+
+        let legend_entries: Vec<(&str, Rgba<u8>, Swatch)> = self.series
+            .iter()
+            .zip(colors)
+            .filter(|(series, _)| !series.name.is_empty())
+            .map(|(series, color)| (series.name, color, Swatch::Box))
+            .collect();
+        draw_legend(backend, &self.layout, &legend_entries, self.legend_position, &plotted);
+
+        Ok(())
+    }
+}
+
+/// Fills the rectangle between two opposite corners.
+fn fill_bar(backend: &mut dyn Backend, corner_a: (f32, f32), corner_b: (f32, f32), color: Rgba<u8>) {
+    let (left, right) = (corner_a.0.min(corner_b.0), corner_a.0.max(corner_b.0));
+    let (top, bottom) = (corner_a.1.min(corner_b.1), corner_a.1.max(corner_b.1));
+
+    backend.fill_rect((left, top), (right - left, bottom - top), color);
+}
@@ -0,0 +1,119 @@
+//! Categorical axes, which give every name along an axis an equal band
+//!
+//! Bar graphs always have a categorical axis, and scatter graphs get one along their x-axis when they're
+//! loaded with `(category, value)` pairs. Categories are laid out in the order they were first loaded,
+//! unless they're given a [`CategoryOrder`].
+//!
+//! ```rust no_run
+//! use ferrischart::charts::{category::CategoryOrder, ScatterGraph};
+//!
+//! ScatterGraph::build()
+//!     .add_category_series("p50", vec![("checkout", 120.0), ("search", 45.0), ("auth", 30.0)])
+//!     .add_category_series("p99", vec![("checkout", 480.0), ("search", 210.0), ("auth", 95.0)])
+//!     .set_category_order(CategoryOrder::Sorted)
+//!     .draw("latency.png")
+//!     .unwrap();
+//! ```
+
+/// The order categories are laid out along their axis.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum CategoryOrder {
+    /// In the order they were first loaded.
+    #[default]
+    Insertion,
+    /// Sorted by their names.
+    Sorted,
+    /// In the order they're listed. Categories that aren't listed come after the ones that are, in the
+    /// order they were first loaded, and names that aren't categories are ignored.
+    Custom(Vec<String>),
+}
+
+impl CategoryOrder {
+    /// Lays categories out in the order of `names`.
+    pub fn custom<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        CategoryOrder::Custom(names.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Adds the method that orders a chart's categories to a chart's `impl` block.
+/// The chart needs a `categories: Categories` field.
+macro_rules! category_methods {
+    () => {
+        /// Sets the order categories are laid out in along their axis: from left to right along an x-axis,
+        /// or from top to bottom down a y-axis. Defaults to [`CategoryOrder::Insertion`](crate::charts::category::CategoryOrder::Insertion).
+        pub fn set_category_order(mut self, order: $crate::charts::category::CategoryOrder) -> Self {
+            self.categories.order = order;
+
+            self
+        }
+    };
+}
+
+pub(crate) use category_methods;
+
+/// The names along a categorical axis, each known by its index in the order it was first loaded.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Categories {
+    names: Vec<String>,
+    pub(crate) order: CategoryOrder,
+}
+
+impl Categories {
+    /// The index of the category called `name`, adding it if it hasn't been seen before.
+    pub(crate) fn index(&mut self, name: String) -> usize {
+        match self.names.iter().position(|existing| *existing == name) {
+            Some(index) => index,
+            None => {
+                self.names.push(name);
+                self.names.len() - 1
+            }
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Forgets every category, keeping the order.
+    pub(crate) fn clear(&mut self) {
+        self.names.clear();
+    }
+
+    /// The index of every category, in the order they're laid out along the axis.
+    pub(crate) fn ordered(&self) -> Vec<usize> {
+        let mut indexes: Vec<usize> = (0..self.names.len()).collect();
+        match &self.order {
+            CategoryOrder::Insertion => {}
+            CategoryOrder::Sorted => indexes.sort_by(|a, b| self.names[*a].cmp(&self.names[*b])),
+            // the sort is stable, so anything that isn't listed keeps the order it was loaded in
+            CategoryOrder::Custom(listed) => indexes.sort_by_key(|index| {
+                listed.iter().position(|name| *name == self.names[*index]).unwrap_or(listed.len())
+            }),
+        }
+
+        indexes
+    }
+
+    /// The names of the categories, in the order they're laid out along the axis.
+    pub(crate) fn ordered_names(&self) -> Vec<String> {
+        self.ordered().into_iter().map(|index| self.names[index].clone()).collect()
+    }
+
+    /// Where along the axis each category is laid out, by its index.
+    pub(crate) fn positions(&self) -> Vec<usize> {
+        let mut positions = vec![0; self.names.len()];
+        for (position, index) in self.ordered().into_iter().enumerate() {
+            positions[index] = position;
+        }
+
+        positions
+    }
+}
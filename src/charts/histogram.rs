@@ -0,0 +1,281 @@
+//! Histogram implementation
+
+use crate::{
+    backend::Backend,
+    data::{missing_values_methods, MissingValues, Numeric},
+    error::{ChartErrors, ChartResult},
+    math::binning::{bin_counts, bin_edges, freedman_diaconis, scott, sturges}
+};
+use super::{
+    layout::{layout_methods, Layout},
+    axes::{axis_methods, axes_style_methods, draw_axes, AxisLabels, AxisOptions}
+};
+
+/// The most bins a histogram can be divided into, which keeps a tiny bin width from using up all of the memory.
+const MAX_BINS: usize = 100_000;
+
+/// How samples are divided into bins.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Bins {
+    /// A fixed number of equally sized bins.
+    Count(usize),
+    /// Bins of a fixed width, starting from the lowest sample.
+    Width(f64),
+    /// Picks the number of bins with Sturges' rule. See [`sturges`].
+    #[default]
+    Sturges,
+    /// Picks the width of the bins with Scott's normal reference rule. See [`scott`].
+    Scott,
+    /// Picks the width of the bins with the Freedman–Diaconis rule. See [`freedman_diaconis`].
+    FreedmanDiaconis,
+}
+
+/// How each bin is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistogramStyle {
+    /// A filled bar for every bin.
+    #[default]
+    Bars,
+    /// A single unfilled outline tracing the top of every bin.
+    Step,
+}
+
+/// A struct that aids in building histograms.
+pub struct Histogram<'a> {
+    title: &'a str,
+    x_axis_text: &'a str,
+    y_axis_text: &'a str,
+    samples: Vec<f64>,
+    bins: Bins,
+    density: bool,
+    cumulative: bool,
+    style: HistogramStyle,
+    missing_values: MissingValues,
+    x_axis: AxisOptions<'a>,
+    y_axis: AxisOptions<'a>,
+    layout: Layout
+}
+
+impl<'a> Default for Histogram<'a> {
+    fn default() -> Self {
+        Self {
+            title: "unset",
+            x_axis_text: "unset",
+            y_axis_text: "unset",
+            samples: vec![],
+            bins: Bins::Sturges,
+            density: false,
+            cumulative: false,
+            style: HistogramStyle::Bars,
+            missing_values: MissingValues::Skip,
+            x_axis: AxisOptions::default(),
+            y_axis: AxisOptions::default(),
+            layout: Layout::default()
+        }
+    }
+}
+
+impl<'a> Histogram<'a> {
+    /// Begin the process of building a histogram.
+    /// Initially returns the default value.
+    pub fn build() -> Self {
+        Self::default()
+    }
+
+    /// Loads the raw samples that will be binned, which can be any numeric type.
+    pub fn load_data<I>(mut self, data: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Numeric,
+    {
+        self.samples = data.into_iter().map(Numeric::to_f64).collect();
+
+        self
+    }
+
+    /// Sets how samples are divided into bins. Defaults to [`Bins::Sturges`].
+    pub fn set_bins(mut self, bins: Bins) -> Self {
+        self.bins = bins;
+
+        self
+    }
+
+    /// Sets whether each bin shows a probability density instead of a count,
+    /// so that the area of all bins adds up to 1.
+    pub fn set_density(mut self, density: bool) -> Self {
+        self.density = density;
+
+        self
+    }
+
+    /// Sets whether each bin also counts the samples of every bin before it.
+    pub fn set_cumulative(mut self, cumulative: bool) -> Self {
+        self.cumulative = cumulative;
+
+        self
+    }
+
+    /// Sets whether bins are drawn as filled bars or as a step outline.
+    pub fn set_style(mut self, style: HistogramStyle) -> Self {
+        self.style = style;
+
+        self
+    }
+
+    /// How many of the loaded points won't be drawn. See [`MissingValues`] for what's counted.
+    pub fn dropped_points(&self) -> usize {
+        self.samples.iter().filter(|sample| !sample.is_finite()).count()
+    }
+
+    /// Works out the edges of every bin and the height each bin should be drawn at.
+    fn heights(&self) -> ChartResult<(Vec<f64>, Vec<f64>)> {
+        self.missing_values.check("", self.samples.iter().map(|sample| sample.is_finite()))?;
+        let samples: Vec<f64> = self.samples.iter().copied().filter(|sample| sample.is_finite()).collect();
+        if samples.is_empty() {
+            return Err(ChartErrors::EmptyData);
+        }
+
+        let mut sorted = samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let (mut min, mut max) = (sorted[0], sorted[sorted.len() - 1]);
+        if min == max {
+            // every sample is the same, so give the single bin some room
+            min -= 0.5;
+            max += 0.5;
+        }
+        let range = max - min;
+
+        // the rules can pick a width of 0 when most samples are the same, which just gets a single bin
+        let bin_count_from_width = |width: f64| {
+            if width > 0.0 {
+                (range / width).ceil().clamp(1.0, MAX_BINS as f64) as usize
+            } else {
+                1
+            }
+        };
+        let (bin_count, max) = match self.bins {
+            Bins::Count(count) if count > MAX_BINS => {
+                return Err(ChartErrors::InvalidRange(format!("{count} bins is more than the most of {MAX_BINS}")));
+            }
+            Bins::Count(count) => (count.max(1), max),
+            Bins::Width(width) if !(width > 0.0 && width.is_finite()) => {
+                return Err(ChartErrors::InvalidRange(format!("bins have to be wider than 0, not {width}")));
+            }
+            Bins::Width(width) if range / width > MAX_BINS as f64 => {
+                return Err(ChartErrors::InvalidRange(format!(
+                    "bins {width} wide would split the samples into more than {MAX_BINS} bins"
+                )));
+            }
+            // fixed widths don't have to divide the range evenly, so the last bin may end after the highest sample
+            Bins::Width(width) => {
+                let count = bin_count_from_width(width);
+                (count, min + width * count as f64)
+            }
+            Bins::Sturges => (sturges(samples.len()), max),
+            // there's always a sample by now, but a single bin is the fallback if there weren't
+            Bins::Scott => (scott(&samples).map_or(1, bin_count_from_width), max),
+            Bins::FreedmanDiaconis => (freedman_diaconis(&samples).map_or(1, bin_count_from_width), max),
+        };
+
+        let edges = bin_edges(min, max, bin_count);
+        let counts = bin_counts(&samples, &edges);
+
+        let sample_count = samples.len() as f64;
+        let mut running_total = 0.0;
+        let heights = counts
+            .iter()
+            .zip(edges.windows(2))
+            .map(|(count, edge)| {
+                let mut height = *count as f64;
+                if self.cumulative {
+                    running_total += height;
+                    height = running_total;
+                }
+                if self.density {
+                    // a cumulative density ends at 1, rather than having an area of 1
+                    height /= sample_count;
+                    if !self.cumulative {
+                        height /= edge[1] - edge[0];
+                    }
+                }
+                height
+            })
+            .collect();
+
+        Ok((edges, heights))
+    }
+
+    axis_methods!();
+
+    axes_style_methods!();
+
+    missing_values_methods!();
+
+    layout_methods!();
+
+    /// Draws the graph with any [`Backend`], including ones implemented outside of ferrischart.
+    /// Fails with [`ChartErrors::EmptyData`] if there are no samples to bin, with [`ChartErrors::NonFiniteValue`]
+    /// if a sample is missing and [`MissingValues::Error`] is set, and with [`ChartErrors::InvalidRange`]
+    /// if the bins can't be drawn.
+    pub fn draw_to(mut self, backend: &mut dyn Backend) -> ChartResult<()> {
+        self.layout.apply_overrides();
+        let (edges, heights) = self.heights()?;
+        self.layout.draw_background(backend);
+
+        let x_labels = self.x_axis.ticks(edges.iter().copied());
+        // every bin starts from 0
+        let y_labels = self.y_axis.ticks(heights.iter().copied().chain([0.0]));
+
+        // draw the axis lines, labels and text
+        let axes = draw_axes(
+            backend,
+            &self.layout,
+            self.title,
+            (self.x_axis_text, self.y_axis_text),
+            AxisLabels::Values(&x_labels),
+            AxisLabels::Values(&y_labels)
+        );
+
+        let color = self.layout.theme.palette.color(0);
+        let (top_left, size) = axes.plot_area();
+        // a log scale never reaches 0, so bins start from the bottom of the plotting area instead
+        let baseline = if self.y_axis.scale.can_show(0.0) {
+            axes.y_position(0.0)
+        } else {
+            top_left.1 + size.1
+        };
+        backend.clip(top_left, size);
+        match self.style {
+            HistogramStyle::Bars => {
+                for (edge, height) in edges.windows(2).zip(&heights) {
+                    let (left, top) = axes.clamp_corner((axes.x_position(edge[0]), axes.y_position(*height)));
+                    let (right, baseline) = axes.clamp_corner((axes.x_position(edge[1]), baseline));
+
+                    // outline each bar so that neighbouring bins can be told apart
+                    let bar_size = (right - left, baseline - top);
+                    backend.fill_rect((left, top), bar_size, color);
+                    if let Some(edge_color) = self.layout.theme.edge_color {
+                        backend.stroke_rect((left, top), bar_size, self.layout.line_width(), edge_color);
+                    }
+                }
+            }
+            HistogramStyle::Step => {
+                // trace up from the baseline, across the top of every bin, and back down again
+                let mut outline = vec![(axes.x_position(edges[0]), baseline)];
+                for (edge, height) in edges.windows(2).zip(&heights) {
+                    let top = axes.y_position(*height);
+                    outline.push((axes.x_position(edge[0]), top));
+                    outline.push((axes.x_position(edge[1]), top));
+                }
+                outline.push((axes.x_position(edges[edges.len() - 1]), baseline));
+
+                for segment in outline.windows(2) {
+                    backend.line(segment[0], segment[1], self.layout.line_width(), color);
+                }
+            }
+        }
+        backend.reset_clip();
+
+        Ok(())
+    }
+}
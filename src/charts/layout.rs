@@ -0,0 +1,310 @@
+//! Canvas size, margins, scale and theme shared by every chart
+
+use crate::{backend::Backend, font::FontFamily};
+use super::{
+    axes::{GridLines, Spines, TickDirection},
+    palette::Palette,
+    theme::Theme
+};
+
+/// The DPI a scale factor of `1.0` corresponds to.
+pub const BASE_DPI: f32 = 100.0;
+
+/// The space between the edges of a chart and its plotting area, where the title, axis text and labels go.
+/// Margins are measured before the chart is scaled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Margins {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl Margins {
+    /// Creates margins going clockwise from the top, the same order CSS uses.
+    pub fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self { top, right, bottom, left }
+    }
+
+    /// Creates the same margin on every side.
+    pub fn uniform(margin: f32) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+}
+
+impl Default for Margins {
+    fn default() -> Self {
+        Self::uniform(50.0)
+    }
+}
+
+/// Where everything on a chart goes, how big it's drawn, and what it looks like.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Layout {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) margins: Margins,
+    pub(crate) scale: f32,
+    pub(crate) theme: Theme,
+    pub(crate) overrides: ThemeOverrides,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            width: 500,
+            height: 500,
+            margins: Margins::default(),
+            scale: 1.0,
+            theme: Theme::default(),
+            overrides: ThemeOverrides::default(),
+        }
+    }
+}
+
+impl Layout {
+    /// Layers the parts of the theme that were set on the chart itself over the theme, ready for drawing.
+    /// Charts call this first thing in `draw_to`, so that it doesn't matter whether they were set before or after the theme.
+    pub(crate) fn apply_overrides(&mut self) {
+        std::mem::take(&mut self.overrides).apply(&mut self.theme);
+    }
+
+    /// The size of the image the chart is drawn onto, once it's been scaled.
+    pub(crate) fn pixel_size(&self) -> (u32, u32) {
+        let scaled = |length: u32| ((length as f32 * self.scale).round() as u32).max(1);
+        (scaled(self.width), scaled(self.height))
+    }
+
+    /// Scales a length, such as a font size or tick size, by the chart's scale factor.
+    pub(crate) fn scaled(&self, length: f32) -> f32 {
+        length * self.scale
+    }
+
+    /// The top-left corner and size of the area inside of the margins, where data is plotted.
+    pub(crate) fn plot_area(&self) -> ((f32, f32), (f32, f32)) {
+        let (width, height) = self.pixel_size();
+        let (left, top) = (self.scaled(self.margins.left), self.scaled(self.margins.top));
+        let right = width as f32 - self.scaled(self.margins.right);
+        let bottom = height as f32 - self.scaled(self.margins.bottom);
+
+        // margins wider than the chart leave a single pixel to plot in, rather than a negative area
+        ((left, top), ((right - left).max(1.0), (bottom - top).max(1.0)))
+    }
+
+    /// The size of titles.
+    pub(crate) fn title_size(&self) -> f32 {
+        self.scaled(self.theme.title_size)
+    }
+
+    /// The size of the text naming each axis.
+    pub(crate) fn axis_text_size(&self) -> f32 {
+        self.scaled(self.theme.axis_text_size)
+    }
+
+    /// The size of axis labels, legend entries and other small text.
+    pub(crate) fn label_size(&self) -> f32 {
+        self.scaled(self.theme.label_size)
+    }
+
+    /// The width of axis lines, outlines and plotted lines.
+    pub(crate) fn line_width(&self) -> f32 {
+        self.scaled(self.theme.line_width)
+    }
+
+    /// Fills the whole canvas with the theme's background colour.
+    pub(crate) fn draw_background(&self, backend: &mut dyn Backend) {
+        let (width, height) = self.pixel_size();
+        backend.fill_rect((0.0, 0.0), (width as f32, height as f32), self.theme.background);
+    }
+}
+
+/// The parts of a chart's theme that were set on the chart itself, such as with `set_palette`.
+/// They're kept apart from the theme until the chart is drawn, so that `set_theme` doesn't replace them.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct ThemeOverrides {
+    pub(crate) palette: Option<Palette>,
+    pub(crate) title_font: Option<FontFamily>,
+    pub(crate) axis_text_font: Option<FontFamily>,
+    pub(crate) label_font: Option<FontFamily>,
+    pub(crate) x_grid: Option<GridLines>,
+    pub(crate) y_grid: Option<GridLines>,
+    pub(crate) x_minor_grid: Option<GridLines>,
+    pub(crate) y_minor_grid: Option<GridLines>,
+    pub(crate) spines: Option<Spines>,
+    pub(crate) tick_direction: Option<TickDirection>,
+    pub(crate) tick_length: Option<f32>,
+}
+
+impl ThemeOverrides {
+    /// Replaces every part of `theme` that was set.
+    fn apply(self, theme: &mut Theme) {
+        if let Some(palette) = self.palette {
+            theme.palette = palette;
+        }
+        if let Some(font) = self.title_font {
+            theme.title_font = font;
+        }
+        if let Some(font) = self.axis_text_font {
+            theme.axis_text_font = font;
+        }
+        if let Some(font) = self.label_font {
+            theme.label_font = font;
+        }
+        // grids are only ever turned on, since there's no setter that turns them off
+        if let Some(grid) = self.x_grid {
+            theme.x_grid = Some(grid);
+        }
+        if let Some(grid) = self.y_grid {
+            theme.y_grid = Some(grid);
+        }
+        if let Some(grid) = self.x_minor_grid {
+            theme.x_minor_grid = Some(grid);
+        }
+        if let Some(grid) = self.y_minor_grid {
+            theme.y_minor_grid = Some(grid);
+        }
+        if let Some(spines) = self.spines {
+            theme.spines = spines;
+        }
+        if let Some(direction) = self.tick_direction {
+            theme.tick_direction = direction;
+        }
+        if let Some(length) = self.tick_length {
+            theme.tick_length = length;
+        }
+    }
+}
+
+/// Adds the methods that title, size and save a chart to a chart's `impl` block.
+/// The chart needs `title: &'a str` and `layout: Layout` fields, and a
+/// `draw_to(self, &mut dyn Backend) -> ChartResult<()>` method.
+macro_rules! layout_methods {
+    () => {
+        /// Sets a title for the chart.
+        pub fn set_title(mut self, title: &'a str) -> Self {
+            self.title = title;
+
+            self
+        }
+
+        /// Sets the colours, text sizes and axes styling the chart is drawn with. Defaults to [`Theme::default`].
+        /// The palette, fonts, grid lines, spines and ticks set with the chart's own methods are drawn over
+        /// the theme, whether they're set before or after it.
+        ///
+        /// [`Theme::default`]: crate::charts::theme::Theme::default
+        pub fn set_theme(mut self, theme: $crate::charts::theme::Theme) -> Self {
+            self.layout.theme = theme;
+
+            self
+        }
+
+        /// Sets the colours the chart's series, wedges or bins are given, in the order they were loaded.
+        /// Histograms are drawn in its first colour. Defaults to the theme's palette.
+        pub fn set_palette(mut self, palette: $crate::charts::palette::Palette) -> Self {
+            self.layout.overrides.palette = Some(palette);
+
+            self
+        }
+
+        /// Draws every piece of text on the chart in `font`.
+        pub fn set_font(mut self, font: $crate::font::FontFamily) -> Self {
+            self.layout.overrides.title_font = Some(font.clone());
+            self.layout.overrides.axis_text_font = Some(font.clone());
+            self.layout.overrides.label_font = Some(font);
+
+            self
+        }
+
+        /// Draws the title in `font`. Defaults to the theme's title font.
+        pub fn set_title_font(mut self, font: $crate::font::FontFamily) -> Self {
+            self.layout.overrides.title_font = Some(font);
+
+            self
+        }
+
+        /// Draws the text naming each axis in `font`. Defaults to the theme's axis text font.
+        pub fn set_axis_text_font(mut self, font: $crate::font::FontFamily) -> Self {
+            self.layout.overrides.axis_text_font = Some(font);
+
+            self
+        }
+
+        /// Draws axis labels, legend entries and other small text in `font`. Defaults to the theme's label font.
+        pub fn set_label_font(mut self, font: $crate::font::FontFamily) -> Self {
+            self.layout.overrides.label_font = Some(font);
+
+            self
+        }
+
+        /// Sets the width and height of the chart, before it's scaled. Defaults to 500x500.
+        pub fn set_size(mut self, width: u32, height: u32) -> Self {
+            self.layout.width = width.max(1);
+            self.layout.height = height.max(1);
+
+            self
+        }
+
+        /// Sets the space between the edges of the chart and its plotting area.
+        /// Defaults to 50 on every side.
+        pub fn set_margins(mut self, margins: $crate::charts::layout::Margins) -> Self {
+            self.layout.margins = margins;
+
+            self
+        }
+
+        /// Multiplies the size of everything on the chart, including its text and line widths, by `scale`.
+        /// A 500x500 chart with a scale of `2.0` is drawn onto a 1000x1000 image, looking exactly the same.
+        pub fn set_scale(mut self, scale: f32) -> Self {
+            if scale > 0.0 && scale.is_finite() {
+                self.layout.scale = scale;
+            }
+
+            self
+        }
+
+        /// Sets the scale of the chart from the DPI it will be printed at, where
+        /// [`BASE_DPI`](crate::charts::layout::BASE_DPI) is a scale of `1.0`.
+        pub fn set_dpi(self, dpi: f32) -> Self {
+            self.set_scale(dpi / $crate::charts::layout::BASE_DPI)
+        }
+
+        /// Draws the chart and saves to a file path.
+        /// Paths ending in `.svg` are drawn as an SVG document, every other path
+        /// is rasterized into the image format its extension names.
+        pub fn draw(self, path: &str) -> $crate::error::ChartResult<()> {
+            let (width, height) = self.layout.pixel_size();
+            $crate::backend::save(path, width, height, |backend| self.draw_to(backend))
+        }
+
+        /// Draws the chart as an SVG document and saves to a file path.
+        pub fn draw_svg(self, path: &str) -> $crate::error::ChartResult<()> {
+            let (width, height) = self.layout.pixel_size();
+            $crate::backend::save_svg(path, width, height, |backend| self.draw_to(backend))
+        }
+
+        /// Draws the chart into an in-memory image, without touching the filesystem.
+        pub fn render(self) -> $crate::error::ChartResult<$crate::image::RgbaImage> {
+            let (width, height) = self.layout.pixel_size();
+            $crate::backend::render(width, height, |backend| self.draw_to(backend))
+        }
+
+        /// Draws the chart and encodes it into `writer` in the given image format,
+        /// such as straight into the body of an HTTP response.
+        pub fn write_to<W: std::io::Write + std::io::Seek>(
+            self,
+            writer: &mut W,
+            format: $crate::image::ImageFormat
+        ) -> $crate::error::ChartResult<()> {
+            let (width, height) = self.layout.pixel_size();
+            $crate::backend::write_to(writer, format, width, height, |backend| self.draw_to(backend))
+        }
+
+        /// The width and height of the image the chart is drawn onto, once it's been scaled.
+        /// Backends passed to `draw_to` should be this size.
+        pub fn pixel_size(&self) -> (u32, u32) {
+            self.layout.pixel_size()
+        }
+    };
+}
+
+pub(crate) use layout_methods;
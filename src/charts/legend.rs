@@ -0,0 +1,167 @@
+//! Legend drawing shared by the graphs that plot several named series
+
+use image::Rgba;
+
+use crate::{backend::Backend, math::distance::euclidean_distance};
+use super::{layout::Layout, marker::Marker};
+
+/// Where a legend is drawn, inside of the plotting area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LegendPosition {
+    /// Whichever corner covers the fewest plotted points, trying the top-right corner first.
+    #[default]
+    Auto,
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+    /// Don't draw a legend at all.
+    Hidden,
+}
+
+/// Adds the method that places a chart's legend to a chart's `impl` block.
+/// The chart needs a `legend_position: LegendPosition` field.
+macro_rules! legend_methods {
+    () => {
+        /// Sets where the legend is drawn. Defaults to [`LegendPosition::Auto`](crate::charts::legend::LegendPosition::Auto),
+        /// which keeps it clear of the data where it can.
+        pub fn set_legend_position(mut self, position: $crate::charts::legend::LegendPosition) -> Self {
+            self.legend_position = position;
+
+            self
+        }
+    };
+}
+
+pub(crate) use legend_methods;
+
+/// How a legend entry shows which colour belongs to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Swatch {
+    /// A short horizontal line, used by line graphs.
+    Line,
+    /// A small filled square, used by bar graphs.
+    Box,
+    /// The marker and outline the series is drawn with, used by scatter graphs.
+    Marker(Marker, Option<(Rgba<u8>, f32)>),
+}
+
+/// Draws a legend in a corner of the plotting area.
+/// Each entry is a swatch in the series' colour, followed by its name.
+/// `plotted` holds the pixel positions of what's been drawn, so that [`LegendPosition::Auto`] can keep out of the way.
+pub(crate) fn draw_legend(
+    backend: &mut dyn Backend,
+    layout: &Layout,
+    entries: &[(&str, Rgba<u8>, Swatch)],
+    position: LegendPosition,
+    plotted: &[(f32, f32)]
+) {
+    if entries.is_empty() || position == LegendPosition::Hidden {
+        return;
+    }
+
+    let label_scale = layout.label_size();
+    let swatch_width = layout.scaled(20.0);
+    let spacing = layout.scaled(5.0);
+    let entry_height = layout.scaled(15.0);
+    let line_width = layout.line_width();
+
+    let widest_name = entries
+        .iter()
+        .map(|(name, _, _)| backend.text_size(name, &layout.theme.label_font, label_scale).0)
+        .max()
+        .unwrap_or(0);
+    let legend_width = spacing * 3.0 + swatch_width + widest_name as f32;
+    let legend_height = spacing * 2.0 + entry_height * entries.len() as f32;
+    let ((plot_left, plot_top), (plot_width, plot_height)) = layout.plot_area();
+
+    // each corner leaves a small gap between the legend and the axes
+    let corner = |position: LegendPosition| {
+        let left = plot_left + spacing;
+        let right = plot_left + plot_width - spacing - legend_width;
+        let top = plot_top + spacing;
+        let bottom = plot_top + plot_height - spacing - legend_height;
+        match position {
+            LegendPosition::TopLeft => (left, top),
+            LegendPosition::BottomRight => (right, bottom),
+            LegendPosition::BottomLeft => (left, bottom),
+            _ => (right, top),
+        }
+    };
+    let (legend_x, legend_y) = match position {
+        LegendPosition::Auto => {
+            let covered = |(x, y): (f32, f32)| {
+                plotted
+                    .iter()
+                    .filter(|(px, py)| *px >= x && *px <= x + legend_width && *py >= y && *py <= y + legend_height)
+                    .count()
+            };
+            // min_by_key keeps the first of any ties, so the top-right corner wins when nothing's in the way
+            [LegendPosition::TopRight, LegendPosition::TopLeft, LegendPosition::BottomRight, LegendPosition::BottomLeft]
+                .into_iter()
+                .map(corner)
+                .min_by_key(|top_left| covered(*top_left))
+                .unwrap_or_else(|| corner(LegendPosition::TopRight))
+        }
+        position => corner(position),
+    };
+
+    // clear whatever was drawn underneath, then outline the legend
+    let legend_size = (legend_width, legend_height);
+    backend.fill_rect((legend_x, legend_y), legend_size, layout.theme.legend_background);
+    if let Some(border) = layout.theme.legend_border {
+        backend.stroke_rect((legend_x, legend_y), legend_size, line_width, border);
+    }
+
+    for (i, (name, color, swatch)) in entries.iter().enumerate() {
+        let entry_y = legend_y + spacing + entry_height * i as f32;
+        let swatch_x = legend_x + spacing;
+        match swatch {
+            Swatch::Line => {
+                let swatch_y = entry_y + entry_height / 2.0;
+                backend.line((swatch_x, swatch_y), (swatch_x + swatch_width, swatch_y), layout.scaled(2.0), *color);
+            }
+            Swatch::Box => {
+                let side = entry_height - layout.scaled(4.0);
+                backend.fill_rect(
+                    (swatch_x + (swatch_width - side) / 2.0, entry_y + layout.scaled(2.0)),
+                    (side, side),
+                    *color
+                );
+            }
+            Swatch::Marker(marker, stroke) => {
+                let center = (swatch_x + swatch_width / 2.0, entry_y + entry_height / 2.0);
+                marker.draw(backend, center, layout.scaled(4.0), *color, *stroke);
+            }
+        }
+        backend.text(
+            (legend_x + spacing * 2.0 + swatch_width, entry_y),
+            name,
+            &layout.theme.label_font,
+            label_scale,
+            layout.theme.text_color
+        );
+    }
+}
+
+/// Points every `step` pixels along a line, for letting [`LegendPosition::Auto`] know where a line has been drawn.
+pub(crate) fn trace_line(start: (f32, f32), end: (f32, f32), step: f32) -> impl Iterator<Item = (f32, f32)> {
+    let length = euclidean_distance(start, end);
+    let count = (length / step.max(1.0)).ceil().min(1000.0) as usize;
+    (0..=count).map(move |i| {
+        let t = if count == 0 { 0.0 } else { i as f32 / count as f32 };
+        (start.0 + (end.0 - start.0) * t, start.1 + (end.1 - start.1) * t)
+    })
+}
+
+/// Points every `step` pixels across a rectangle between two opposite corners, for letting
+/// [`LegendPosition::Auto`] know where a shape has been filled in.
+pub(crate) fn trace_area(corner_a: (f32, f32), corner_b: (f32, f32), step: f32) -> Vec<(f32, f32)> {
+    let ((left, top), (right, bottom)) = (
+        (corner_a.0.min(corner_b.0), corner_a.1.min(corner_b.1)),
+        (corner_a.0.max(corner_b.0), corner_a.1.max(corner_b.1))
+    );
+    trace_line((left, top), (left, bottom), step)
+        .flat_map(|(_, y)| trace_line((left, y), (right, y), step))
+        .collect()
+}
@@ -0,0 +1,219 @@
+//! Line Graph implementation
+
+use crate::{
+    backend::Backend,
+    data::{missing_values_methods, IntoPoint, MissingValues, Numeric},
+    error::{ChartErrors, ChartResult}
+};
+use super::{
+    layout::{layout_methods, Layout},
+    axes::{axis_methods, axes_style_methods, draw_axes, AxisLabels, AxisOptions},
+    legend::{draw_legend, legend_methods, trace_line, LegendPosition, Swatch},
+    ticks::FixedLocator
+};
+
+/// A named set of points that is drawn as a single line.
+struct Series<'a> {
+    name: &'a str,
+    data: Vec<(f64, f64)>
+}
+
+/// A struct that aids in building line graphs.
+pub struct LineGraph<'a> {
+    title: &'a str,
+    x_axis_text: &'a str,
+    y_axis_text: &'a str,
+    x_axis: AxisOptions<'a>,
+    y_axis: AxisOptions<'a>,
+    series: Vec<Series<'a>>,
+    legend_position: LegendPosition,
+    missing_values: MissingValues,
+    layout: Layout
+}
+
+impl<'a> Default for LineGraph<'a> {
+    fn default() -> Self {
+        Self {
+            title: "unset",
+            x_axis_text: "unset",
+            y_axis_text: "unset",
+            x_axis: AxisOptions::default(),
+            y_axis: AxisOptions::default(),
+            series: vec![],
+            legend_position: LegendPosition::default(),
+            missing_values: MissingValues::Skip,
+            layout: Layout::default()
+        }
+    }
+}
+
+impl<'a> LineGraph<'a> {
+    /// Begin the process of building a line graph.
+    /// Initially returns the default value.
+    pub fn build() -> Self {
+        Self::default()
+    }
+
+    /// Loads data as a single, unnamed line, from `(x, y)` pairs of any numeric types.
+    /// This replaces any series previously added.
+    pub fn load_data<I>(mut self, data: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoPoint,
+    {
+        self.series.clear();
+        self.add_series("", data)
+    }
+
+    /// Loads data as a single, unnamed line, from a column of x values and a column of y values.
+    /// If one column is longer than the other, its extra values are left out.
+    /// This replaces any series previously added.
+    pub fn load_columns<X, Y>(mut self, xs: X, ys: Y) -> Self
+    where
+        X: IntoIterator,
+        X::Item: Numeric,
+        Y: IntoIterator,
+        Y::Item: Numeric,
+    {
+        self.series.clear();
+        self.add_columns("", xs, ys)
+    }
+
+    /// Adds a named line to the graph. Labels are picked to fit every line when the graph is drawn.
+    /// Lines with a non-empty name are listed in the graph's legend.
+    pub fn add_series<I>(mut self, name: &'a str, data: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoPoint,
+    {
+        // points are kept in the order they were given, so that missing values are reported by their position,
+        // and are only sorted into the order they're joined up in when the line is drawn
+        let transformed = data
+            .into_iter()
+            .map(IntoPoint::into_point)
+            .collect();
+
+        self.series.push(Series { name, data: transformed });
+
+        self
+    }
+
+    /// Adds a named line to the graph, from a column of x values and a column of y values.
+    /// If one column is longer than the other, its extra values are left out.
+    pub fn add_columns<X, Y>(self, name: &'a str, xs: X, ys: Y) -> Self
+    where
+        X: IntoIterator,
+        X::Item: Numeric,
+        Y: IntoIterator,
+        Y::Item: Numeric,
+    {
+        self.add_series(name, xs.into_iter().zip(ys))
+    }
+
+    /// How many of the loaded points won't be drawn. See [`MissingValues`] for what's counted.
+    pub fn dropped_points(&self) -> usize {
+        self.series
+            .iter()
+            .flat_map(|series| series.data.iter())
+            .filter(|(x, y)| !self.x_axis.scale.can_show(*x) || !self.y_axis.scale.can_show(*y))
+            .count()
+    }
+
+    /// Sets the labels for each axis.
+    /// This is the same as using a [`FixedLocator`] for each axis. Only use this method if you know
+    /// what you're doing since labels are already picked to fit every line.
+    pub fn set_labels(mut self, x_labels: Vec<f64>, y_labels: Vec<f64>) -> Self {
+        self.x_axis.locator = Some(Box::new(FixedLocator(x_labels)));
+        self.y_axis.locator = Some(Box::new(FixedLocator(y_labels)));
+
+        self
+    }
+
+    axis_methods!();
+
+    legend_methods!();
+
+    axes_style_methods!();
+
+    missing_values_methods!();
+
+    layout_methods!();
+
+    /// Draws the graph with any [`Backend`], including ones implemented outside of ferrischart.
+    /// Fails with [`ChartErrors::EmptyData`] if none of the lines have any points, and with
+    /// [`ChartErrors::NonFiniteValue`] if a point is missing a value and [`MissingValues::Error`] is set.
+    pub fn draw_to(mut self, backend: &mut dyn Backend) -> ChartResult<()> {
+        self.layout.apply_overrides();
+        if self.series.iter().all(|series| series.data.is_empty()) {
+            return Err(ChartErrors::EmptyData);
+        }
+        for series in &self.series {
+            let finite = series.data.iter().map(|(x, y)| x.is_finite() && y.is_finite());
+            self.missing_values.check(series.name, finite)?;
+        }
+        self.layout.draw_background(backend);
+
+        // labels need to cover every series, then we can draw the axis lines, labels and text
+        let points = || self.series.iter().flat_map(|s| s.data.iter());
+        let x_labels = self.x_axis.ticks(points().map(|(x, _)| *x));
+        let y_labels = self.y_axis.ticks(points().map(|(_, y)| *y));
+        let axes = draw_axes(
+            backend,
+            &self.layout,
+            self.title,
+            (self.x_axis_text, self.y_axis_text),
+            AxisLabels::Values(&x_labels),
+            AxisLabels::Values(&y_labels)
+        );
+
+        // connect each point to the next one with an anti-aliased line
+        // lines leaving the plotting area (such as with custom labels) are cut off at the axes
+        let (top_left, size) = axes.plot_area();
+        backend.clip(top_left, size);
+        let mut legend_entries = vec![];
+        let mut plotted = vec![];
+        for (i, series) in self.series.iter().enumerate() {
+            let color = self.layout.theme.palette.color(i);
+            // points a scale can't show, such as missing values or 0 on a log scale, are left out of the line
+            // and when breaking the line, the points either side of them are no longer joined up
+            let mut runs = vec![];
+            let mut run = vec![];
+            for point in &connection_order(&series.data) {
+                if self.x_axis.scale.can_show(point.0) && self.y_axis.scale.can_show(point.1) {
+                    run.push(axes.position(*point));
+                } else if self.missing_values == MissingValues::Break {
+                    runs.push(std::mem::take(&mut run));
+                }
+            }
+            runs.push(run);
+            for positions in runs.iter().filter(|positions| positions.len() > 1) {
+                backend.polyline(positions, self.layout.line_width(), color);
+                for segment in positions.windows(2) {
+                    plotted.extend(trace_line(segment[0], segment[1], self.layout.scaled(5.0)));
+                }
+            }
+
+            if !series.name.is_empty() {
+                legend_entries.push((series.name, color, Swatch::Line));
+            }
+        }
+
+        backend.reset_clip();
+
+        draw_legend(backend, &self.layout, &legend_entries, self.legend_position, &plotted);
+
+        Ok(())
+    }
+}
+
+/// The points of a line in the order they're joined up, which is by their x values. Points without an x value
+/// have nowhere to be sorted to, so they stay where they were given and the points either side of them
+/// are sorted separately, which lets [`MissingValues::Break`] break the line where they were.
+fn connection_order(data: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut ordered = data.to_vec();
+    for run in ordered.split_mut(|(x, _)| !x.is_finite()) {
+        run.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    ordered
+}
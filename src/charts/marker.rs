@@ -0,0 +1,120 @@
+//! Markers, the shapes that points are drawn as
+
+use std::f32::consts::PI;
+
+use image::Rgba;
+
+use crate::backend::Backend;
+
+/// The shape a point is drawn as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Marker {
+    #[default]
+    Circle,
+    Square,
+    /// A triangle pointing upwards.
+    Triangle,
+    Diamond,
+    /// A diagonal cross, like an x.
+    Cross,
+    /// An upright cross, like a plus sign.
+    Plus,
+    /// An outlined circle, which leaves overlapping points easier to tell apart.
+    HollowCircle,
+    HollowSquare,
+    HollowTriangle,
+    HollowDiamond,
+}
+
+impl Marker {
+    /// Whether the marker is only an outline.
+    pub fn is_hollow(&self) -> bool {
+        matches!(self, Marker::HollowCircle | Marker::HollowSquare | Marker::HollowTriangle | Marker::HollowDiamond)
+    }
+
+    /// The corners of the marker's shape, centred on `center` and reaching `radius` pixels out from it.
+    /// Markers that are only lines have no outline.
+    fn outline(&self, center: (f32, f32), radius: f32) -> Vec<(f32, f32)> {
+        let (x, y) = center;
+        match self {
+            Marker::Circle | Marker::HollowCircle => {
+                // enough corners that the outline looks round at any size
+                let corners = ((radius * 2.0) as usize).clamp(12, 64);
+                (0..corners)
+                    .map(|i| {
+                        let angle = i as f32 / corners as f32 * 2.0 * PI;
+                        (x + radius * angle.cos(), y + radius * angle.sin())
+                    })
+                    .collect()
+            }
+            Marker::Square | Marker::HollowSquare => {
+                vec![(x - radius, y - radius), (x + radius, y - radius), (x + radius, y + radius), (x - radius, y + radius)]
+            }
+            // pointing up, with its centre of mass on the point
+            Marker::Triangle | Marker::HollowTriangle => {
+                vec![(x, y - radius), (x + radius * 0.866, y + radius * 0.5), (x - radius * 0.866, y + radius * 0.5)]
+            }
+            Marker::Diamond | Marker::HollowDiamond => vec![(x, y - radius), (x + radius, y), (x, y + radius), (x - radius, y)],
+            Marker::Cross | Marker::Plus => vec![],
+        }
+    }
+
+    /// The outline of both arms of a cross, centred on `center` and reaching `radius` pixels out from it.
+    fn arms(&self, center: (f32, f32), radius: f32) -> Vec<(f32, f32)> {
+        let (x, y) = center;
+        // crosses are all lines, so they're drawn a bit thicker to be as easy to spot as the filled shapes
+        let half = (radius / 2.0).max(1.0) / 2.0;
+        // the arms have square ends, sticking out past `radius` by half of their width like the ends of lines do
+        let reach = radius + half;
+        let plus = [
+            (-half, -reach),
+            (half, -reach),
+            (half, -half),
+            (reach, -half),
+            (reach, half),
+            (half, half),
+            (half, reach),
+            (-half, reach),
+            (-half, half),
+            (-reach, half),
+            (-reach, -half),
+            (-half, -half),
+        ];
+
+        // a diagonal cross is a plus sign turned by an eighth of a turn
+        let (sin, cos) = if *self == Marker::Cross { (PI / 4.0).sin_cos() } else { (0.0, 1.0) };
+        plus.iter().map(|(dx, dy)| (x + dx * cos - dy * sin, y + dx * sin + dy * cos)).collect()
+    }
+
+    /// Draws the marker centred on `center`, reaching `radius` pixels out from it.
+    /// Filled markers are outlined with `stroke` as a colour and width, if there is one.
+    /// Hollow markers are outlined in `color` unless `stroke` says otherwise, and crosses ignore `stroke`.
+    pub(crate) fn draw(
+        &self,
+        backend: &mut dyn Backend,
+        center: (f32, f32),
+        radius: f32,
+        color: Rgba<u8>,
+        stroke: Option<(Rgba<u8>, f32)>
+    ) {
+        match self {
+            // both arms are one shape, so a see-through cross isn't painted twice where they meet
+            Marker::Cross | Marker::Plus => backend.polygon(&self.arms(center, radius), color),
+            // circles are drawn as circles rather than polygons, which keeps them perfectly round
+            Marker::Circle => backend.circle(center, radius, color),
+            _ if !self.is_hollow() => backend.polygon(&self.outline(center, radius), color),
+            _ => {}
+        }
+
+        let stroke = match stroke {
+            Some(stroke) => stroke,
+            None if self.is_hollow() => (color, (radius / 3.0).max(1.0)),
+            None => return,
+        };
+        let mut outline = self.outline(center, radius);
+        if let Some(first) = outline.first().copied() {
+            outline.push(first);
+            backend.polyline(&outline, stroke.1, stroke.0);
+        }
+    }
+}
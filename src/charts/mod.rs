@@ -1,12 +1,36 @@
 //! A module holding the various types of graphs and charts
 
+pub mod axes;
+pub mod bar;
+pub mod category;
+pub mod histogram;
+pub mod layout;
+pub mod legend;
+pub mod line;
+pub mod marker;
+pub mod palette;
+pub mod pie;
+pub mod scale;
 pub mod scatter;
+pub mod theme;
+pub mod ticks;
+#[cfg(feature = "chrono")]
+pub mod time;
 
+pub use bar::BarGraph;
+pub use histogram::Histogram;
+pub use layout::Margins;
+pub use line::LineGraph;
+pub use palette::Palette;
+pub use pie::PieChart;
 pub use scatter::ScatterGraph;
+pub use theme::Theme;
 
 use image::{Rgb, Rgba};
 use rand::Rng;
 
+/// A random opaque colour.
+#[deprecated(note = "use Palette")]
 pub fn random_rgb() -> Rgb<u8> {
     let mut rng = rand::thread_rng();
     let (r, g, b) = (
@@ -17,6 +41,8 @@ pub fn random_rgb() -> Rgb<u8> {
     Rgb([r, g, b])
 }
 
+/// A random colour with the given opacity.
+#[deprecated(note = "use Palette")]
 pub fn random_rgba(alpha: u8) -> Rgba<u8> {
     let mut rng = rand::thread_rng();
     let (r, g, b) = (
@@ -0,0 +1,146 @@
+//! Palettes, the sets of colours that charts are drawn in
+
+use image::Rgba;
+
+/// A set of colours to draw a chart in.
+///
+/// Qualitative palettes are a handful of distinct colours, given out in order to each series.
+/// Sequential palettes (colormaps) blend smoothly from one end to the other, which suits showing
+/// a value through colour.
+///
+/// ```rust
+/// use ferrischart::charts::palette::Palette;
+/// use image::Rgba;
+///
+/// assert_eq!(Palette::Tab10.color(0), Rgba([31, 119, 180, 255]));
+/// // colormaps are sampled from 0.0 (the start) to 1.0 (the end)
+/// assert_eq!(Palette::Viridis.map(1.0), Rgba([253, 231, 37, 255]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Palette {
+    /// The 10 colours matplotlib and Tableau use by default.
+    #[default]
+    Tab10,
+    /// ColorBrewer's 8 soft, pastel-like colours.
+    Set2,
+    /// Okabe and Ito's 8 colours, which stay distinct for the most common kinds of colour blindness.
+    OkabeIto,
+    /// A colormap from dark purple through blue and green to yellow, evenly bright as it goes.
+    Viridis,
+    /// A colormap from black through purple and red to pale yellow.
+    Magma,
+    /// A colormap from dark blue to yellow, designed to look the same with and without colour blindness.
+    Cividis,
+    /// Your own colours, given out in order. Treated as a qualitative palette.
+    Custom(
+        #[cfg_attr(feature = "serde", serde(with = "super::theme::hex_color::list"))]
+        Vec<Rgba<u8>>
+    ),
+}
+
+const TAB10: [[u8; 3]; 10] = [
+    [31, 119, 180], [255, 127, 14], [44, 160, 44], [214, 39, 40], [148, 103, 189],
+    [140, 86, 75], [227, 119, 194], [127, 127, 127], [188, 189, 34], [23, 190, 207],
+];
+
+const SET2: [[u8; 3]; 8] = [
+    [102, 194, 165], [252, 141, 98], [141, 160, 203], [231, 138, 195],
+    [166, 216, 84], [255, 217, 47], [229, 196, 148], [179, 179, 179],
+];
+
+const OKABE_ITO: [[u8; 3]; 8] = [
+    [230, 159, 0], [86, 180, 233], [0, 158, 115], [240, 228, 66],
+    [0, 114, 178], [213, 94, 0], [204, 121, 167], [0, 0, 0],
+];
+
+// colormaps are stored as 10 evenly spaced stops and blended in between
+const VIRIDIS: [[u8; 3]; 10] = [
+    [68, 1, 84], [72, 40, 120], [62, 74, 137], [49, 104, 142], [38, 130, 142],
+    [31, 158, 137], [53, 183, 121], [109, 205, 89], [180, 222, 44], [253, 231, 37],
+];
+
+const MAGMA: [[u8; 3]; 10] = [
+    [0, 0, 4], [24, 15, 62], [69, 16, 119], [114, 31, 129], [159, 47, 127],
+    [205, 64, 113], [241, 96, 93], [253, 149, 103], [254, 201, 141], [252, 253, 191],
+];
+
+const CIVIDIS: [[u8; 3]; 10] = [
+    [0, 32, 77], [0, 51, 111], [57, 72, 107], [87, 92, 109], [112, 113, 115],
+    [138, 135, 121], [166, 157, 117], [196, 181, 108], [228, 207, 91], [255, 234, 70],
+];
+
+impl Palette {
+    /// The colours the palette is made of.
+    pub fn colors(&self) -> Vec<Rgba<u8>> {
+        let stops: &[[u8; 3]] = match self {
+            Palette::Tab10 => &TAB10,
+            Palette::Set2 => &SET2,
+            Palette::OkabeIto => &OKABE_ITO,
+            Palette::Viridis => &VIRIDIS,
+            Palette::Magma => &MAGMA,
+            Palette::Cividis => &CIVIDIS,
+            Palette::Custom(colors) => return colors.clone(),
+        };
+
+        stops.iter().map(|[r, g, b]| Rgba([*r, *g, *b, 255])).collect()
+    }
+
+    /// Whether the palette blends smoothly from one end to the other, rather than being a set of distinct colours.
+    pub fn is_sequential(&self) -> bool {
+        matches!(self, Palette::Viridis | Palette::Magma | Palette::Cividis)
+    }
+
+    /// The colour given to the `index`th series.
+    /// Qualitative palettes start again from the first colour once they run out.
+    /// Sequential palettes step along the colormap, so that neighbouring series are told apart.
+    pub fn color(&self, index: usize) -> Rgba<u8> {
+        if self.is_sequential() {
+            // the golden ratio spreads any number of steps evenly along the colormap, without
+            // knowing how many series there are
+            let position = (index as f32 * 0.618_034).fract();
+            return self.map(position);
+        }
+
+        let colors = self.colors();
+        if colors.is_empty() {
+            return Rgba([0, 0, 0, 255]);
+        }
+        colors[index % colors.len()]
+    }
+
+    /// The colour at `position` along the palette, from `0.0` at the start to `1.0` at the end.
+    /// Sequential palettes blend between their colours, qualitative ones pick the nearest colour.
+    pub fn map(&self, position: f32) -> Rgba<u8> {
+        let colors = self.colors();
+        if colors.is_empty() {
+            return Rgba([0, 0, 0, 255]);
+        }
+
+        // NaN is treated as the start of the palette
+        let position = if position.is_nan() { 0.0 } else { position.clamp(0.0, 1.0) };
+        let last = (colors.len() - 1) as f32;
+        if !self.is_sequential() {
+            return colors[(position * last).round() as usize];
+        }
+
+        let scaled = position * last;
+        let (index, fraction) = (scaled.floor() as usize, scaled.fract());
+        if index as f32 >= last {
+            return colors[colors.len() - 1];
+        }
+        let (from, to) = (colors[index], colors[index + 1]);
+        let blend = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * fraction).round() as u8;
+        Rgba([blend(from[0], to[0]), blend(from[1], to[1]), blend(from[2], to[2]), blend(from[3], to[3])])
+    }
+
+    /// The colour `value` maps onto, where `min` is the start of the palette and `max` is the end.
+    pub fn map_value(&self, value: f64, min: f64, max: f64) -> Rgba<u8> {
+        if max > min {
+            self.map(((value - min) / (max - min)) as f32)
+        } else {
+            self.map(0.5)
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! Pie Chart implementation
+
+use std::f32::consts::PI;
+
+use image::Rgba;
+
+use crate::{
+    backend::Backend,
+    data::{missing_values_methods, MissingValues, Numeric},
+    error::{ChartErrors, ChartResult},
+    font::FontFamily
+};
+use super::layout::{layout_methods, Layout};
+
+/// Wedges smaller than this fraction of the pie have their percentage drawn outside
+/// of the pie, with a leader line pointing at the wedge.
+const SMALL_WEDGE: f32 = 0.05;
+
+/// A single labelled slice of the pie.
+struct Wedge {
+    label: String,
+    value: f64
+}
+
+/// A struct that aids in building pie and donut charts.
+pub struct PieChart<'a> {
+    title: &'a str,
+    wedges: Vec<Wedge>,
+    explode: Vec<(String, f32)>,
+    inner_radius: f32,
+    start_angle: f32,
+    missing_values: MissingValues,
+    layout: Layout
+}
+
+impl<'a> Default for PieChart<'a> {
+    fn default() -> Self {
+        Self {
+            title: "unset",
+            wedges: vec![],
+            explode: vec![],
+            inner_radius: 0.0,
+            start_angle: 90.0,
+            missing_values: MissingValues::Skip,
+            layout: Layout::default()
+        }
+    }
+}
+
+impl<'a> PieChart<'a> {
+    /// Begin the process of building a pie chart.
+    /// Initially returns the default value.
+    pub fn build() -> Self {
+        Self::default()
+    }
+
+    /// Loads `(label, value)` pairs, one for every wedge of the pie.
+    /// Each wedge's size is its share of the total of every value.
+    pub fn load_data<I, S, T>(mut self, data: I) -> Self
+    where
+        I: IntoIterator<Item = (S, T)>,
+        S: Into<String>,
+        T: Numeric,
+    {
+        self.wedges = data
+            .into_iter()
+            .map(|(label, value)| Wedge {
+                label: label.into(),
+                value: value.to_f64()
+            })
+            .collect();
+
+        self
+    }
+
+    /// Turns the pie into a donut by cutting out its centre.
+    /// `inner_radius` is a fraction of the pie's radius, from `0.0` (a pie) up to `0.95`, which leaves a thin ring.
+    pub fn set_inner_radius(mut self, inner_radius: f32) -> Self {
+        self.inner_radius = inner_radius.clamp(0.0, 0.95);
+
+        self
+    }
+
+    /// Sets the angle, in degrees counter-clockwise from 3 o'clock, that the first wedge starts at.
+    /// Wedges are laid out counter-clockwise from there. Defaults to `90.0` (12 o'clock).
+    pub fn set_start_angle(mut self, degrees: f32) -> Self {
+        self.start_angle = degrees;
+
+        self
+    }
+
+    /// Pulls the wedge with the given label away from the centre of the pie.
+    /// `offset` is a fraction of the pie's radius. This can be called before or after the data is loaded.
+    pub fn set_explode(mut self, label: &str, offset: f32) -> Self {
+        self.explode.push((label.to_string(), offset.max(0.0)));
+
+        self
+    }
+
+    /// How many of the loaded points won't be drawn. See [`MissingValues`] for what's counted.
+    pub fn dropped_points(&self) -> usize {
+        self.wedges.iter().filter(|wedge| !(wedge.value.is_finite() && wedge.value > 0.0)).count()
+    }
+
+    missing_values_methods!();
+
+    layout_methods!();
+
+    /// Draws the chart with any [`Backend`], including ones implemented outside of ferrischart.
+    /// Fails with [`ChartErrors::EmptyData`] if no wedge has a positive value, and with [`ChartErrors::NonFiniteValue`]
+    /// if a wedge's value is missing and [`MissingValues::Error`] is set.
+    pub fn draw_to(mut self, backend: &mut dyn Backend) -> ChartResult<()> {
+        self.layout.apply_overrides();
+        self.missing_values.check("", self.wedges.iter().map(|wedge| wedge.value.is_finite()))?;
+        // negative and missing values don't take up any of the pie, so there may be nothing to draw
+        let share = |wedge: &Wedge| if wedge.value.is_finite() { wedge.value.max(0.0) } else { 0.0 };
+        let total: f64 = self.wedges.iter().map(share).sum();
+        if total <= 0.0 {
+            return Err(ChartErrors::EmptyData);
+        }
+
+        let layout = &self.layout;
+        let text_color = layout.theme.text_color;
+        let (title_font, label_font) = (&layout.theme.title_font, &layout.theme.label_font);
+        layout.draw_background(backend);
+
+        let scale = layout.title_size();
+        let label_scale = layout.label_size();
+        let ((left, top), (width, height)) = layout.plot_area();
+
+        // the title sits centered above the pie, the same way it does above the axes of other graphs
+        let (title_width, title_height) = backend.text_size(self.title, title_font, scale);
+        backend.text(
+            (left + (width - title_width as f32) / 2.0, top - title_height as f32),
+            self.title,
+            title_font,
+            scale,
+            text_color
+        );
+
+        // the pie fills the plotting area, nudged down away from the title
+        // and leaving room around it for labels
+        let center = (left + width / 2.0, top + height / 2.0 + layout.scaled(20.0));
+        let radius = width.min(height) / 2.0 * 0.65;
+        let inner_radius = radius * self.inner_radius;
+
+        let mut angle = self.start_angle.to_radians();
+        let mut outside_labels = vec![];
+
+        for (i, wedge) in self.wedges.iter().enumerate() {
+            // only the share of the pie needs to be as precise as the data, angles are drawn in pixels
+            let fraction = (share(wedge) / total) as f32;
+            if fraction == 0.0 {
+                continue;
+            }
+            let sweep = fraction * 2.0 * PI;
+            let (start, end) = (angle, angle + sweep);
+            let mid = start + sweep / 2.0;
+            angle = end;
+
+            // exploded wedges are moved outwards along the line through their middle
+            // the last offset set for a label wins
+            let explode = self.explode.iter().rev().find(|(label, _)| *label == wedge.label);
+            let offset = explode.map_or(0.0, |(_, explode)| *explode) * radius;
+            let wedge_center = point_at(center, offset, mid);
+
+            let color = self.layout.theme.palette.color(i);
+            let outline = wedge_outline(wedge_center, radius, inner_radius, start, end);
+            backend.polygon(&outline, color);
+
+            // the category label always sits just outside of the wedge
+            let percentage = format!("{:.1}%", fraction * 100.0);
+            if fraction >= SMALL_WEDGE {
+                let label_anchor = point_at(wedge_center, radius + layout.scaled(10.0), mid);
+                draw_side_aligned_text(backend, label_font, label_scale, label_anchor, mid, &wedge.label, text_color);
+
+                // there's enough room to fit the percentage inside the wedge, half way through its ring
+                let (x, y) = point_at(wedge_center, (radius + inner_radius) / 2.0, mid);
+                let (text_width, text_height) = backend.text_size(&percentage, label_font, label_scale);
+                backend.text(
+                    (x - (text_width / 2) as f32, y - (text_height / 2) as f32),
+                    &percentage,
+                    label_font,
+                    label_scale,
+                    contrasting_text_color(color, layout.theme.background)
+                );
+            } else {
+                // small wedges are labelled further out, once we know where every other small label goes
+                outside_labels.push((
+                    format!("{} ({})", wedge.label, percentage),
+                    point_at(wedge_center, radius, mid),
+                    point_at(wedge_center, radius + layout.scaled(25.0), mid),
+                    mid
+                ));
+            }
+        }
+
+        // neighbouring small wedges would have their labels drawn on top of each other,
+        // so on each side of the pie we sort the labels top to bottom and push them apart
+        let (_, line_height) = backend.text_size("0%", label_font, label_scale);
+        let min_gap = line_height as f32 + layout.scaled(2.0);
+        for right_side in [true, false] {
+            let mut side: Vec<_> = outside_labels
+                .iter()
+                .filter(|(_, _, _, mid)| (mid.cos() >= 0.0) == right_side)
+                .collect();
+            side.sort_by(|a, b| a.2.1.total_cmp(&b.2.1));
+
+            let mut previous_y = f32::MIN;
+            for (text, wedge_point, anchor, mid) in side {
+                let y = anchor.1.max(previous_y + min_gap);
+                previous_y = y;
+                let anchor = (anchor.0, y);
+
+                // leader line from the edge of the wedge to the label
+                backend.line(*wedge_point, anchor, layout.line_width(), text_color);
+                draw_side_aligned_text(backend, label_font, label_scale, anchor, *mid, text, text_color);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The point `distance` pixels away from `origin` in the direction of `angle` (radians counter-clockwise from 3 o'clock).
+/// Since (0,0) is the top-left of the image, the y-component is flipped.
+fn point_at(origin: (f32, f32), distance: f32, angle: f32) -> (f32, f32) {
+    (origin.0 + distance * angle.cos(), origin.1 - distance * angle.sin())
+}
+
+/// Works out the polygon for a wedge between two angles.
+/// Wedges of a donut trace their outer arc and then come back along their inner arc.
+fn wedge_outline(center: (f32, f32), radius: f32, inner_radius: f32, start: f32, end: f32) -> Vec<(f32, f32)> {
+    // roughly one point every degree keeps the arc smooth
+    let steps = ((end - start).to_degrees().ceil() as usize).max(2);
+    let arc = |r: f32| (0..=steps).map(move |i| point_at(center, r, start + (end - start) * i as f32 / steps as f32));
+
+    let mut outline: Vec<(f32, f32)> = arc(radius).collect();
+    if inner_radius > 0.0 {
+        outline.extend(arc(inner_radius).collect::<Vec<_>>().into_iter().rev());
+    } else {
+        outline.push(center);
+    }
+
+    outline
+}
+
+/// Draws text next to `anchor`, extending away from the pie so that it never overlaps it.
+/// Text on the right half of the pie starts at the anchor, text on the left half ends at it.
+fn draw_side_aligned_text(
+    backend: &mut dyn Backend,
+    font: &FontFamily,
+    size: f32,
+    anchor: (f32, f32),
+    angle: f32,
+    text: &str,
+    color: Rgba<u8>
+) {
+    let (text_width, text_height) = backend.text_size(text, font, size);
+    let x = if angle.cos() >= 0.0 { anchor.0 } else { anchor.0 - text_width as f32 };
+    backend.text((x, anchor.1 - (text_height / 2) as f32), text, font, size, color);
+}
+
+/// Picks black or white text, whichever stands out more against a wedge's colour.
+/// Translucent wedges are blended with the chart's `backdrop`, which is itself shown over white when translucent.
+fn contrasting_text_color(fill: Rgba<u8>, backdrop: Rgba<u8>) -> Rgba<u8> {
+    let over = |color: Rgba<u8>, under: [f32; 3]| {
+        let alpha = color[3] as f32 / 255.0;
+        [0, 1, 2].map(|i| color[i] as f32 * alpha + under[i] * (1.0 - alpha))
+    };
+    let [r, g, b] = over(fill, over(backdrop, [255.0; 3]));
+    let luminance = 0.299 * r + 0.587 * g + 0.114 * b;
+    if luminance > 150.0 {
+        Rgba([0, 0, 0, 255])
+    } else {
+        Rgba([255, 255, 255, 255])
+    }
+}
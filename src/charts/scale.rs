@@ -0,0 +1,172 @@
+//! Scales, which map data values onto pixels
+
+use super::ticks::{
+    DecimalFormatter, Formatter, Locator, LogLocator, NiceLocator, PowerFormatter, SymLogLocator
+};
+
+/// How values are spread along an axis.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AxisScale {
+    /// Equal differences between values take up equal space.
+    #[default]
+    Linear,
+    /// Equal ratios between values take up equal space, so 1 to 10 is as long as 10 to 100 with a base of 10.
+    /// Only positive values can be shown, and bases that aren't above 1 are treated as 10.
+    Log(f64),
+    /// Logarithmic in both directions away from 0, but close to linear between `-linthresh` and `linthresh`
+    /// so that 0 and negative values can be shown too.
+    SymLog(f64),
+    /// Linear, but with values decreasing along the axis instead of increasing.
+    Reversed,
+}
+
+impl AxisScale {
+    /// Converts a value into the space where the axis is linear.
+    pub fn transform(&self, value: f64) -> f64 {
+        match *self {
+            AxisScale::Linear | AxisScale::Reversed => value,
+            // nothing at or below 0 has a logarithm, so those values end up far off of the axis
+            AxisScale::Log(base) => value.max(f64::MIN_POSITIVE).log(log_base(base)),
+            AxisScale::SymLog(linthresh) => {
+                let linthresh = symlog_threshold(linthresh);
+                value.signum() * (1.0 + value.abs() / linthresh).log10()
+            }
+        }
+    }
+
+    /// Converts a value back out of the space where the axis is linear. The opposite of [`transform`](AxisScale::transform).
+    pub fn untransform(&self, value: f64) -> f64 {
+        match *self {
+            AxisScale::Linear | AxisScale::Reversed => value,
+            AxisScale::Log(base) => log_base(base).powf(value),
+            AxisScale::SymLog(linthresh) => {
+                let linthresh = symlog_threshold(linthresh);
+                value.signum() * linthresh * (10f64.powf(value.abs()) - 1.0)
+            }
+        }
+    }
+
+    /// Whether the axis can show `value` at all.
+    pub fn can_show(&self, value: f64) -> bool {
+        value.is_finite() && !(matches!(self, AxisScale::Log(_)) && value <= 0.0)
+    }
+
+    /// The locator an axis with this scale uses unless it's given another one.
+    pub fn locator(&self) -> Box<dyn Locator> {
+        match *self {
+            AxisScale::Linear | AxisScale::Reversed => Box::new(NiceLocator::default()),
+            AxisScale::Log(base) => Box::new(LogLocator { base: log_base(base) }),
+            AxisScale::SymLog(linthresh) => Box::new(SymLogLocator { linthresh: symlog_threshold(linthresh) }),
+        }
+    }
+
+    /// The formatter an axis with this scale uses unless it's given another one.
+    pub fn formatter(&self) -> Box<dyn Formatter> {
+        match *self {
+            AxisScale::Linear | AxisScale::Reversed => Box::new(DecimalFormatter),
+            AxisScale::Log(base) => Box::new(PowerFormatter { base: log_base(base) }),
+            AxisScale::SymLog(_) => Box::new(PowerFormatter { base: 10.0 }),
+        }
+    }
+}
+
+/// Falls back to base 10 for bases that logarithms can't be taken in.
+fn log_base(base: f64) -> f64 {
+    if base > 1.0 && base.is_finite() {
+        base
+    } else {
+        10.0
+    }
+}
+
+/// Falls back to a threshold of 1 for thresholds that aren't positive.
+fn symlog_threshold(linthresh: f64) -> f64 {
+    if linthresh > 0.0 && linthresh.is_finite() {
+        linthresh
+    } else {
+        1.0
+    }
+}
+
+/// Maps values in a domain onto pixel positions in a range, and back again.
+///
+/// The range can run backwards, which is how the y-axis puts bigger values higher up the image.
+/// Values are spread along the range by an [`AxisScale`].
+///
+/// ```rust
+/// use ferrischart::charts::scale::Scale;
+///
+/// // 0 to 10 along a y-axis running from 450 (the bottom) up to 50
+/// let scale = Scale::linear((0.0, 10.0), (450.0, 50.0));
+/// assert_eq!(scale.map(2.5), 350.0);
+/// assert_eq!(scale.invert(350.0), 2.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale {
+    kind: AxisScale,
+    domain: (f64, f64),
+    range: (f32, f32),
+}
+
+impl Scale {
+    /// Creates a scale that maps `domain.0` onto `range.0`, `domain.1` onto `range.1`,
+    /// and everything else in proportion. Values outside of the domain carry on at the same rate.
+    /// A domain with no width is widened by 0.5 either side, so that it can still be mapped.
+    pub fn linear(domain: (f64, f64), range: (f32, f32)) -> Self {
+        Self::new(AxisScale::Linear, domain, range)
+    }
+
+    /// Creates a scale that maps `domain.0` onto `range.0` and `domain.1` onto `range.1`,
+    /// spreading everything else out in the way `kind` describes.
+    /// [`AxisScale::Reversed`] scales map `domain.0` onto `range.1` instead.
+    pub fn new(kind: AxisScale, domain: (f64, f64), range: (f32, f32)) -> Self {
+        let domain = if domain.0 != domain.1 {
+            domain
+        } else if let AxisScale::Log(_) = kind {
+            // a log scale can't be widened into 0 or below, so it's widened by a step either side instead
+            (kind.untransform(kind.transform(domain.0) - 1.0), kind.untransform(kind.transform(domain.1) + 1.0))
+        } else {
+            (domain.0 - 0.5, domain.1 + 0.5)
+        };
+        let range = if kind == AxisScale::Reversed { (range.1, range.0) } else { range };
+
+        Self { kind, domain, range }
+    }
+
+    /// How values are spread along the scale.
+    pub fn kind(&self) -> AxisScale {
+        self.kind
+    }
+
+    /// The values the scale maps from.
+    pub fn domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    /// The pixel positions the scale maps onto, in the order the domain maps onto them.
+    pub fn range(&self) -> (f32, f32) {
+        self.range
+    }
+
+    /// Converts a value into a pixel position.
+    /// The value is only rounded to the precision of a pixel at the very end, so large values
+    /// such as timestamps that are close together still map onto different pixels.
+    pub fn map(&self, value: f64) -> f32 {
+        let (start, end) = (self.kind.transform(self.domain.0), self.kind.transform(self.domain.1));
+        let fraction = (self.kind.transform(value) - start) / (end - start);
+        let (range_start, range_end) = (self.range.0 as f64, self.range.1 as f64);
+        (range_start + fraction * (range_end - range_start)) as f32
+    }
+
+    /// Converts a pixel position back into the value that maps onto it.
+    pub fn invert(&self, position: f32) -> f64 {
+        if self.range.0 == self.range.1 {
+            // every value maps onto the same pixel, so the middle of the domain is as good as any
+            return (self.domain.0 + self.domain.1) / 2.0;
+        }
+
+        let (start, end) = (self.kind.transform(self.domain.0), self.kind.transform(self.domain.1));
+        let fraction = (position - self.range.0) as f64 / (self.range.1 - self.range.0) as f64;
+        self.kind.untransform(start + fraction * (end - start))
+    }
+}
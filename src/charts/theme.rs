@@ -0,0 +1,347 @@
+//! Themes, which hold everything about how a chart looks apart from its data
+
+use image::Rgba;
+
+use crate::font::FontFamily;
+use super::{
+    axes::{GridLines, Spines, TickDirection},
+    palette::Palette
+};
+
+/// The colours, sizes and axes styling a chart is drawn with.
+///
+/// Every chart starts off with [`Theme::default`], and can be given any other theme with `set_theme`.
+/// Sizes and widths are measured before the chart is scaled.
+///
+/// ```rust
+/// use ferrischart::charts::{theme::Theme, ScatterGraph};
+/// use image::Rgba;
+///
+/// let branded = Theme {
+///     text_color: Rgba([0, 48, 87, 255]),
+///     title_size: 30.0,
+///     ..Theme::minimal()
+/// };
+/// let graph = ScatterGraph::build()
+///     .set_theme(branded)
+///     .load_data(vec![(1.0, 2.0), (2.0, 4.0)]);
+/// ```
+///
+/// With the `serde` feature, themes can also be saved to and loaded from TOML or JSON files.
+/// Colours are written as `"#rrggbb"`, or `"#rrggbbaa"` when they're translucent, and anything
+/// missing from a file is taken from the default theme.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Theme {
+    /// The colour of the whole canvas. Translucent backgrounds stay translucent in images that can hold
+    /// transparency, such as PNG and SVG, and are painted over white in ones that can't, such as JPEG.
+    #[cfg_attr(feature = "serde", serde(with = "hex_color"))]
+    pub background: Rgba<u8>,
+    /// The colour of the plotting area, if it differs from the rest of the canvas.
+    #[cfg_attr(feature = "serde", serde(with = "hex_color::option"))]
+    pub plot_background: Option<Rgba<u8>>,
+    /// The colour of titles, axis text and labels.
+    #[cfg_attr(feature = "serde", serde(with = "hex_color"))]
+    pub text_color: Rgba<u8>,
+    /// The colour of spines and ticks.
+    #[cfg_attr(feature = "serde", serde(with = "hex_color"))]
+    pub axis_color: Rgba<u8>,
+    pub title_font: FontFamily,
+    /// The font of the text naming each axis.
+    pub axis_text_font: FontFamily,
+    /// The font of axis labels, legend entries and other small text.
+    pub label_font: FontFamily,
+    pub title_size: f32,
+    /// The size of the text naming each axis.
+    pub axis_text_size: f32,
+    /// The size of axis labels, legend entries and other small text.
+    pub label_size: f32,
+    /// The width of spines, ticks, outlines and plotted lines.
+    pub line_width: f32,
+    /// The smallest gap between axis labels and their spine.
+    pub label_padding: f32,
+    pub palette: Palette,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub x_grid: Option<GridLines>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub y_grid: Option<GridLines>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub x_minor_grid: Option<GridLines>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub y_minor_grid: Option<GridLines>,
+    pub spines: Spines,
+    pub tick_direction: TickDirection,
+    /// The length of major ticks. Minor ticks are half as long.
+    pub tick_length: f32,
+    #[cfg_attr(feature = "serde", serde(with = "hex_color"))]
+    pub legend_background: Rgba<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "hex_color::option"))]
+    pub legend_border: Option<Rgba<u8>>,
+    /// The colour filled shapes such as histogram bins are outlined in, if they're outlined at all.
+    #[cfg_attr(feature = "serde", serde(with = "hex_color::option"))]
+    pub edge_color: Option<Rgba<u8>>,
+}
+
+impl Default for Theme {
+    /// Black text and axes on white, with spines along the left and bottom and no grid.
+    fn default() -> Self {
+        Self {
+            background: Rgba([255, 255, 255, 255]),
+            plot_background: None,
+            text_color: Rgba([0, 0, 0, 255]),
+            axis_color: Rgba([0, 0, 0, 255]),
+            title_font: FontFamily::DejaVuSans,
+            axis_text_font: FontFamily::DejaVuSans,
+            label_font: FontFamily::DejaVuSans,
+            title_size: 25.0,
+            axis_text_size: 25.0,
+            label_size: 12.5,
+            line_width: 1.0,
+            label_padding: 3.0,
+            palette: Palette::Tab10,
+            x_grid: None,
+            y_grid: None,
+            x_minor_grid: None,
+            y_minor_grid: None,
+            spines: Spines::default(),
+            tick_direction: TickDirection::Out,
+            tick_length: 5.0,
+            legend_background: Rgba([255, 255, 255, 255]),
+            legend_border: Some(Rgba([0, 0, 0, 255])),
+            edge_color: Some(Rgba([0, 0, 0, 255])),
+        }
+    }
+}
+
+impl Theme {
+    /// Light text and axes on a dark grey background.
+    pub fn dark() -> Self {
+        let background = Rgba([34, 34, 34, 255]);
+        Self {
+            background,
+            text_color: Rgba([238, 238, 238, 255]),
+            axis_color: Rgba([187, 187, 187, 255]),
+            palette: Palette::Set2,
+            y_grid: Some(GridLines::new(Rgba([68, 68, 68, 255]), 1.0)),
+            legend_background: background,
+            legend_border: Some(Rgba([119, 119, 119, 255])),
+            edge_color: Some(background),
+            ..Self::default()
+        }
+    }
+
+    /// Modelled on R's ggplot2: a grey plotting area criss-crossed with white grid lines, and no spines.
+    pub fn ggplot() -> Self {
+        let white = Rgba([255, 255, 255, 255]);
+        let grid = GridLines::new(white, 1.0);
+        let minor_grid = GridLines::new(white, 0.5);
+        Self {
+            plot_background: Some(Rgba([235, 235, 235, 255])),
+            text_color: Rgba([77, 77, 77, 255]),
+            axis_color: Rgba([51, 51, 51, 255]),
+            axis_text_size: 18.0,
+            palette: Palette::Custom(hex_palette(&[
+                0xE24A33, 0x348ABD, 0x988ED5, 0x777777, 0xFBC15E, 0x8EBA42, 0xFFB5B8,
+            ])),
+            x_grid: Some(grid.clone()),
+            y_grid: Some(grid),
+            x_minor_grid: Some(minor_grid.clone()),
+            y_minor_grid: Some(minor_grid),
+            spines: Spines::none(),
+            tick_length: 3.0,
+            legend_border: None,
+            edge_color: Some(white),
+            ..Self::default()
+        }
+    }
+
+    /// Modelled on seaborn's `darkgrid` style: a blue-grey plotting area with white grid lines and no ticks.
+    pub fn seaborn() -> Self {
+        let white = Rgba([255, 255, 255, 255]);
+        let grid = GridLines::new(white, 1.0);
+        Self {
+            plot_background: Some(Rgba([234, 234, 242, 255])),
+            text_color: Rgba([38, 38, 38, 255]),
+            axis_color: Rgba([38, 38, 38, 255]),
+            axis_text_size: 18.0,
+            palette: Palette::Custom(hex_palette(&[
+                0x4C72B0, 0xDD8452, 0x55A868, 0xC44E52, 0x8172B3,
+                0x937860, 0xDA8BC3, 0x8C8C8C, 0xCCB974, 0x64B5CD,
+            ])),
+            x_grid: Some(grid.clone()),
+            y_grid: Some(grid),
+            spines: Spines::none(),
+            tick_length: 0.0,
+            label_padding: 6.0,
+            legend_background: Rgba([234, 234, 242, 255]),
+            legend_border: None,
+            edge_color: Some(white),
+            ..Self::default()
+        }
+    }
+
+    /// Nothing but the data, light horizontal grid lines and labels.
+    pub fn minimal() -> Self {
+        Self {
+            text_color: Rgba([51, 51, 51, 255]),
+            axis_color: Rgba([153, 153, 153, 255]),
+            title_size: 22.0,
+            axis_text_size: 16.0,
+            y_grid: Some(GridLines::new(Rgba([230, 230, 230, 255]), 1.0)),
+            spines: Spines::none(),
+            tick_length: 0.0,
+            label_padding: 6.0,
+            legend_border: None,
+            edge_color: Some(Rgba([255, 255, 255, 255])),
+            ..Self::default()
+        }
+    }
+
+    /// Bigger text, thicker lines and colours that stay distinct with colour blindness, for projectors and print.
+    pub fn high_contrast() -> Self {
+        Self {
+            title_size: 28.0,
+            label_size: 15.0,
+            line_width: 2.0,
+            palette: Palette::OkabeIto,
+            tick_length: 7.0,
+            label_padding: 4.0,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Theme {
+    /// Reads a theme from a TOML document.
+    pub fn from_toml(text: &str) -> crate::error::ChartResult<Self> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Writes the theme as a TOML document.
+    pub fn to_toml(&self) -> crate::error::ChartResult<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Reads a theme from a JSON document.
+    pub fn from_json(text: &str) -> crate::error::ChartResult<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    /// Writes the theme as a JSON document.
+    pub fn to_json(&self) -> crate::error::ChartResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Loads a theme from a file path. Paths ending in `.json` are read as JSON, every other path as TOML.
+    pub fn load(path: &str) -> crate::error::ChartResult<Self> {
+        let text = std::fs::read_to_string(path)?;
+        if is_json(path) {
+            Self::from_json(&text)
+        } else {
+            Self::from_toml(&text)
+        }
+    }
+
+    /// Saves the theme to a file path, as JSON if the path ends in `.json` and as TOML otherwise.
+    pub fn save(&self, path: &str) -> crate::error::ChartResult<()> {
+        let text = if is_json(path) { self.to_json()? } else { self.to_toml()? };
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+fn is_json(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .map(|extension| extension.eq_ignore_ascii_case("json"))
+        .unwrap_or(false)
+}
+
+/// Turns colours written as `0xRRGGBB` into opaque colours.
+fn hex_palette(colors: &[u32]) -> Vec<Rgba<u8>> {
+    colors
+        .iter()
+        .map(|color| Rgba([(color >> 16) as u8, (color >> 8) as u8, *color as u8, 255]))
+        .collect()
+}
+
+/// Reads and writes colours as `"#rrggbb"`, or `"#rrggbbaa"` when they're translucent.
+#[cfg(feature = "serde")]
+pub(crate) mod hex_color {
+    use image::Rgba;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    fn format(color: &Rgba<u8>) -> String {
+        let [r, g, b, a] = color.0;
+        if a == 255 {
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+        }
+    }
+
+    fn parse<E: Error>(text: &str) -> Result<Rgba<u8>, E> {
+        let invalid = || E::custom(format!("`{}` isn't a colour written as #rrggbb or #rrggbbaa", text));
+        let digits = text.strip_prefix('#').ok_or_else(invalid)?;
+        if !(digits.len() == 6 || digits.len() == 8) || !digits.is_ascii() {
+            return Err(invalid());
+        }
+
+        let channel = |i: usize| u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16).map_err(|_| invalid());
+        let alpha = if digits.len() == 8 { channel(3)? } else { 255 };
+        Ok(Rgba([channel(0)?, channel(1)?, channel(2)?, alpha]))
+    }
+
+    pub(crate) fn serialize<S: Serializer>(color: &Rgba<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format(color))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rgba<u8>, D::Error> {
+        parse(&String::deserialize(deserializer)?)
+    }
+
+    /// Colours that can be left out, written as `"none"`.
+    pub(crate) mod option {
+        use super::*;
+
+        pub(crate) fn serialize<S: Serializer>(color: &Option<Rgba<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+            match color {
+                Some(color) => serializer.serialize_str(&format(color)),
+                None => serializer.serialize_str("none"),
+            }
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Rgba<u8>>, D::Error> {
+            let text = String::deserialize(deserializer)?;
+            if text.eq_ignore_ascii_case("none") {
+                Ok(None)
+            } else {
+                parse(&text).map(Some)
+            }
+        }
+    }
+
+    /// Lists of colours, such as the colours of a custom palette.
+    pub(crate) mod list {
+        use super::*;
+        use serde::ser::SerializeSeq;
+
+        pub(crate) fn serialize<S: Serializer>(colors: &[Rgba<u8>], serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(colors.len()))?;
+            for color in colors {
+                seq.serialize_element(&format(color))?;
+            }
+            seq.end()
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Rgba<u8>>, D::Error> {
+            Vec::<String>::deserialize(deserializer)?
+                .iter()
+                .map(|text| parse(text))
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! Tick locators, which pick the values labelled along an axis
+//!
+//! Every axis asks a [`Locator`] where its ticks go, given the lowest and highest value it has to show.
+//! The default is a [`NiceLocator`], which steps by 1, 2 or 5 times a power of ten, so that a range of
+//! 0 to 10000 is labelled every 2000 rather than every 1.
+//!
+//! ```rust no_run
+//! use ferrischart::charts::{ticks::MultipleLocator, ScatterGraph};
+//!
+//! ScatterGraph::build()
+//!     .load_data(vec![(1200.0, 0.002), (4800.0, 0.004)])
+//!     .set_x_locator(MultipleLocator(500.0))
+//!     .draw("sensors.png")
+//!     .unwrap();
+//! ```
+
+/// Picks where the ticks along an axis go.
+///
+/// Implement this to place ticks in ways ferrischart doesn't cover.
+pub trait Locator {
+    /// The values to put ticks at, for an axis that has to show every value from `min` to `max`.
+    /// Ticks should be in ascending order, and will usually extend a little past `min` and `max`
+    /// so that the data sits inside of them.
+    fn locate(&self, min: f64, max: f64) -> Vec<f64>;
+
+    /// The values to put smaller, unlabelled ticks at, between the ticks from [`locate`](Locator::locate).
+    /// There are none unless a locator says otherwise.
+    fn minor(&self, _min: f64, _max: f64) -> Vec<f64> {
+        vec![]
+    }
+}
+
+/// Turns the values of ticks into the labels drawn next to them.
+///
+/// Implement this to label ticks in ways ferrischart doesn't cover, such as with units.
+pub trait Formatter {
+    /// A label for every tick, in the same order.
+    fn format(&self, ticks: &[f64]) -> Vec<String>;
+}
+
+/// Ticks stepping by 1, 2 or 5 times a power of ten, picking whichever step gives
+/// closest to `target` ticks. Minor ticks split each step into 4 or 5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NiceLocator {
+    pub target: usize,
+}
+
+impl Default for NiceLocator {
+    fn default() -> Self {
+        Self { target: 7 }
+    }
+}
+
+impl NiceLocator {
+    /// The step between ticks covering `min` to `max`.
+    fn step(&self, min: f64, max: f64) -> f64 {
+        let target = self.target.max(2);
+
+        // try the nice steps around the exact step, since rounding the step
+        // alone can leave half as many or 50% more ticks than we were after
+        let mut step = nice_step((max - min) / target as f64 / 10.0);
+        // a step a tenth of the exact one gives ten times too many ticks, and a few steps
+        // up from there is far past giving too few
+        let mut best = step;
+        for _ in 0..8 {
+            let distance = |step| tick_count(step, min, max).abs_diff(target);
+            // ties go to the bigger step, which leaves the axis less crowded
+            if distance(step) <= distance(best) {
+                best = step;
+            }
+            step = next_nice_step(step);
+        }
+
+        best
+    }
+}
+
+impl Locator for NiceLocator {
+    fn locate(&self, min: f64, max: f64) -> Vec<f64> {
+        let (min, max) = widen(min, max);
+        multiples(self.step(min, max), min, max)
+    }
+
+    fn minor(&self, min: f64, max: f64) -> Vec<f64> {
+        let (min, max) = widen(min, max);
+        subdivisions(self.step(min, max), min, max)
+    }
+}
+
+/// Ticks stepping by 1, 2 or 5 times a power of ten, picking the smallest step that needs
+/// no more than `N` ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxNLocator(pub usize);
+
+impl Locator for MaxNLocator {
+    fn locate(&self, min: f64, max: f64) -> Vec<f64> {
+        let (min, max) = widen(min, max);
+        let max_ticks = self.0.max(2);
+
+        // start from a step that's definitely too small, and work up through the nice steps
+        // (a range crossing 0 always needs at least 3 ticks, so we give up eventually)
+        let mut step = nice_step((max - min) / max_ticks as f64 / 10.0);
+        for _ in 0..16 {
+            if tick_count(step, min, max) <= max_ticks {
+                break;
+            }
+            step = next_nice_step(step);
+        }
+
+        multiples(step, min, max)
+    }
+}
+
+/// Ticks at every multiple of a number, such as every 0.25 or every 500.
+/// Minor ticks split each step into 4 or 5.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MultipleLocator(pub f64);
+
+impl Locator for MultipleLocator {
+    fn locate(&self, min: f64, max: f64) -> Vec<f64> {
+        let (min, max) = widen(min, max);
+        // a step far too small for the data would need more ticks than could ever be drawn
+        if !(self.0 > 0.0 && self.0.is_finite()) || tick_count(self.0, min, max) > MAX_TICKS {
+            return NiceLocator::default().locate(min, max);
+        }
+
+        multiples(self.0, min, max)
+    }
+
+    fn minor(&self, min: f64, max: f64) -> Vec<f64> {
+        let (min, max) = widen(min, max);
+        if !(self.0 > 0.0 && self.0.is_finite()) || tick_count(self.0, min, max) > MAX_TICKS {
+            return NiceLocator::default().minor(min, max);
+        }
+
+        subdivisions(self.0, min, max)
+    }
+}
+
+/// Ticks at exactly the given values, whatever the data is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedLocator(pub Vec<f64>);
+
+impl Locator for FixedLocator {
+    fn locate(&self, _: f64, _: f64) -> Vec<f64> {
+        let mut ticks = self.0.clone();
+        ticks.sort_by(f64::total_cmp);
+
+        ticks
+    }
+}
+
+/// Ticks at every power of `base`, for logarithmic axes.
+/// Ranges spanning lots of powers skip some of them, so that there are no more than 10 ticks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogLocator {
+    pub base: f64,
+}
+
+impl LogLocator {
+    /// The first and last exponents covering `min` to `max`, and how many exponents each tick steps by.
+    fn exponents(&self, min: f64, max: f64) -> (i32, i32, i32) {
+        // only positive values have a logarithm
+        let min = min.max(f64::MIN_POSITIVE).log(self.base);
+        let max = max.max(f64::MIN_POSITIVE).log(self.base);
+        let (first, mut last) = ((min + 1e-5).floor() as i32, (max - 1e-5).ceil() as i32);
+        if last <= first {
+            last = first + 1;
+        }
+        let step = ((last - first) as f64 / 9.0).ceil().max(1.0) as i32;
+
+        (first, first + (last - first + step - 1) / step * step, step)
+    }
+}
+
+impl Locator for LogLocator {
+    fn locate(&self, min: f64, max: f64) -> Vec<f64> {
+        let (first, last, step) = self.exponents(min, max);
+        (first..=last)
+            .step_by(step as usize)
+            .map(|exponent| self.base.powi(exponent))
+            .collect()
+    }
+
+    /// Every whole multiple of each power, such as 2, 3, ... 9 and 20, 30, ... 90 with a base of 10.
+    /// Only bases that are whole numbers have these, and only when no powers are skipped.
+    fn minor(&self, min: f64, max: f64) -> Vec<f64> {
+        let (first, last, step) = self.exponents(min, max);
+        if step != 1 || self.base.fract() != 0.0 {
+            return vec![];
+        }
+
+        (first..last)
+            .flat_map(|exponent| {
+                let power = self.base.powi(exponent);
+                (2..self.base as u32).map(move |multiple| power * multiple as f64)
+            })
+            .collect()
+    }
+}
+
+/// Ticks at 0 and at every power of 10 at or above `linthresh` on either side of it, for symlog axes.
+/// Ranges that never get past `linthresh` are ticked like a linear axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymLogLocator {
+    pub linthresh: f64,
+}
+
+impl Locator for SymLogLocator {
+    fn locate(&self, min: f64, max: f64) -> Vec<f64> {
+        let largest = min.abs().max(max.abs());
+        if largest <= self.linthresh {
+            return NiceLocator::default().locate(min, max);
+        }
+
+        // the powers of 10 from the threshold up to past the largest value
+        let first = self.linthresh.log10().ceil() as i32;
+        let last = largest.log10().ceil() as i32;
+        let step = ((last - first) as f64 / 4.0).ceil().max(1.0) as usize;
+        // when skipping powers, the threshold is skipped too, since it sits too close to 0 to be labelled apart from it
+        let mut powers: Vec<f64> = (first..=last)
+            .rev()
+            .step_by(step)
+            .filter(|exponent| step == 1 || *exponent > first)
+            .map(|exponent| 10f64.powi(exponent))
+            .collect();
+        powers.reverse();
+
+        // each side goes up to the first power that covers its end of the data
+        let spacing = 10f64.powi(step as i32);
+        let mut ticks = vec![];
+        if min < 0.0 {
+            ticks.extend(powers.iter().rev().filter(|power| **power / spacing < -min).map(|power| -power));
+        }
+        ticks.push(0.0);
+        if max > 0.0 {
+            ticks.extend(powers.iter().filter(|power| **power / spacing < max));
+        }
+
+        ticks
+    }
+}
+
+/// Labels ticks as plain decimal numbers, with just enough decimal places to tell every one apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DecimalFormatter;
+
+impl Formatter for DecimalFormatter {
+    fn format(&self, ticks: &[f64]) -> Vec<String> {
+        format_ticks(ticks)
+    }
+}
+
+/// Labels powers of `base` by their exponent, such as 10³ and 10⁻², which keeps labels short on log axes.
+/// Ticks that aren't a power of `base` are labelled as decimal numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerFormatter {
+    pub base: f64,
+}
+
+impl Formatter for PowerFormatter {
+    fn format(&self, ticks: &[f64]) -> Vec<String> {
+        let base = self.base;
+        ticks
+            .iter()
+            .map(|tick| {
+                let magnitude = tick.abs();
+                let exponent = magnitude.log(base).round();
+                let is_power = magnitude > 0.0 && ((base.powf(exponent) - magnitude) / magnitude).abs() < 1e-4;
+                if !is_power {
+                    return format_ticks(&[*tick]).remove(0);
+                }
+
+                let sign = if *tick < 0.0 { "-" } else { "" };
+                format!("{}{}{}", sign, format_ticks(&[self.base]).remove(0), superscript(exponent as i32))
+            })
+            .collect()
+    }
+}
+
+/// Writes a whole number in superscript digits.
+fn superscript(number: i32) -> String {
+    number
+        .to_string()
+        .chars()
+        .map(|digit| match digit {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+/// The most ticks a locator steps out, which keeps ranges that can't be divided up sensibly
+/// from taking forever to label.
+pub(crate) const MAX_TICKS: usize = 1000;
+
+/// Gives a range with no width some room, so that it can still be divided into steps.
+fn widen(min: f64, max: f64) -> (f64, f64) {
+    let (min, max) = (min.min(max), min.max(max));
+    if max - min > f64::EPSILON * max.abs() {
+        (min, max)
+    } else {
+        (min - 0.5, max + 0.5)
+    }
+}
+
+/// Rounds a step to the closest of 1, 2 or 5 times a power of ten.
+pub fn nice_step(raw_step: f64) -> f64 {
+    if !(raw_step > 0.0 && raw_step.is_finite()) {
+        return 1.0;
+    }
+
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let fraction = raw_step / magnitude;
+    // these thresholds are the ones from Paul Heckbert's "Nice Numbers for Graph Labels"
+    let nice = if fraction < 1.5 {
+        1.0
+    } else if fraction < 3.0 {
+        2.0
+    } else if fraction < 7.0 {
+        5.0
+    } else {
+        10.0
+    };
+
+    nice * magnitude
+}
+
+/// The nice step after `step`, going 1, 2, 5, 10, 20, 50, ...
+fn next_nice_step(step: f64) -> f64 {
+    let magnitude = 10f64.powf(step.log10().floor());
+    match (step / magnitude).round() as u32 {
+        1 => 2.0 * magnitude,
+        2 => 5.0 * magnitude,
+        _ => 10.0 * magnitude,
+    }
+}
+
+/// How many steps the first and last multiples of `step` covering `min` to `max` are from 0.
+fn multiple_bounds(step: f64, min: f64, max: f64) -> (i64, i64) {
+    // a slightly tolerant floor and ceil, so that a sum like 0.30000000000000004 doesn't add a tick
+    let tolerance = 1e-5;
+    ((min / step + tolerance).floor() as i64, (max / step - tolerance).ceil() as i64)
+}
+
+/// The number of multiples of `step` needed to cover `min` to `max`.
+fn tick_count(step: f64, min: f64, max: f64) -> usize {
+    let (first, last) = multiple_bounds(step, min, max);
+    // ranges too wide to step across, such as -1e308 to 1e308, saturate rather than overflowing
+    (last.saturating_sub(first) as usize).saturating_add(1)
+}
+
+/// Every multiple of `step` from the one at or below `min` to the one at or above `max`.
+/// Ranges that would need more than [`MAX_TICKS`] ticks are only ticked at their ends.
+fn multiples(step: f64, min: f64, max: f64) -> Vec<f64> {
+    if tick_count(step, min, max) > MAX_TICKS {
+        return vec![min, max];
+    }
+    let (first, last) = multiple_bounds(step, min, max);
+
+    // multiplying (rather than adding up steps) keeps rounding errors from building up
+    (first..=last)
+        .map(|i| i as f64 * step)
+        .map(|tick| if tick == 0.0 { 0.0 } else { tick }) // no "-0" labels
+        .collect()
+}
+
+/// The multiples of a fraction of `step` between the multiples of `step` covering `min` to `max`,
+/// leaving out the ones that land on a multiple of `step`.
+/// Steps starting with a 2, like 0.2 or 20, are split into 4, and every other step into 5.
+fn subdivisions(step: f64, min: f64, max: f64) -> Vec<f64> {
+    let magnitude = 10f64.powf(step.log10().floor());
+    let divisions = if (step / magnitude).round() == 2.0 { 4 } else { 5 };
+    if tick_count(step, min, max).saturating_mul(divisions as usize) > MAX_TICKS {
+        return vec![];
+    }
+    let (first, last) = multiple_bounds(step, min, max);
+
+    let minor_step = step / divisions as f64;
+    (first * divisions..=last * divisions)
+        .filter(|i| i % divisions != 0)
+        .map(|i| i as f64 * minor_step)
+        .collect()
+}
+
+/// Formats tick values with just enough decimal places to tell every one apart.
+pub fn format_ticks(ticks: &[f64]) -> Vec<String> {
+    let decimals = (0..=7)
+        .find(|decimals| {
+            let shift = 10f64.powi(*decimals);
+            ticks.iter().all(|tick| {
+                let shifted = *tick * shift;
+                (shifted - shifted.round()).abs() < 1e-3
+            })
+        })
+        .unwrap_or(7) as usize;
+
+    ticks
+        .iter()
+        .map(|tick| {
+            let label = format!("{:.*}", decimals, tick);
+            // rounding a small negative number can leave "-0"
+            if label.trim_start_matches(['-', '0', '.']).is_empty() {
+                label.trim_start_matches('-').to_string()
+            } else {
+                label
+            }
+        })
+        .collect()
+}
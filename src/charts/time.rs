@@ -0,0 +1,269 @@
+//! Time axes, which label timestamps with round dates and times
+//!
+//! Needs the `chrono` feature. Timestamps are plotted as the number of seconds since the Unix epoch, which
+//! is what chrono's date-times are turned into when a chart is loaded with them. `set_x_time_axis` and
+//! `set_y_time_axis` then tick the axis at round times, such as every 15 minutes, every midnight or the
+//! first of every month, and label them in the given timezone.
+//!
+//! ```rust no_run
+//! use chrono::{FixedOffset, TimeZone, Utc};
+//! use ferrischart::charts::LineGraph;
+//!
+//! let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+//! let readings: Vec<_> = (0..48)
+//!     .map(|hour| (start + chrono::TimeDelta::hours(hour), 20.0 + (hour % 24) as f64 / 4.0))
+//!     .collect();
+//!
+//! LineGraph::build()
+//!     .load_data(readings)
+//!     .set_x_time_axis(FixedOffset::east_opt(9 * 3600).unwrap())
+//!     .draw("temperature.png")
+//!     .unwrap();
+//! ```
+
+use std::fmt::{Display, Write};
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Timelike};
+
+use super::ticks::{format_ticks, nice_step, Formatter, Locator, NiceLocator, MAX_TICKS};
+
+/// A length of time ticks can step by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+impl Unit {
+    /// Roughly how many seconds the unit lasts, which is only used to pick a step.
+    fn seconds(self) -> f64 {
+        match self {
+            Unit::Second => 1.0,
+            Unit::Minute => 60.0,
+            Unit::Hour => 3600.0,
+            Unit::Day => 86_400.0,
+            Unit::Month => 2_629_746.0,
+            Unit::Year => 31_556_952.0,
+        }
+    }
+}
+
+/// The steps ticks can be spaced by, from the smallest up. Each one divides evenly into the next unit,
+/// so that ticks always land on round times. Past the last step, years are stepped like numbers.
+const STEPS: [(Unit, u32); 24] = [
+    (Unit::Second, 1),
+    (Unit::Second, 2),
+    (Unit::Second, 5),
+    (Unit::Second, 10),
+    (Unit::Second, 15),
+    (Unit::Second, 30),
+    (Unit::Minute, 1),
+    (Unit::Minute, 2),
+    (Unit::Minute, 5),
+    (Unit::Minute, 10),
+    (Unit::Minute, 15),
+    (Unit::Minute, 30),
+    (Unit::Hour, 1),
+    (Unit::Hour, 2),
+    (Unit::Hour, 3),
+    (Unit::Hour, 6),
+    (Unit::Hour, 12),
+    (Unit::Day, 1),
+    (Unit::Day, 2),
+    (Unit::Day, 7),
+    (Unit::Day, 14),
+    (Unit::Month, 1),
+    (Unit::Month, 3),
+    (Unit::Month, 6),
+];
+
+/// Ticks at round dates and times in `timezone`, picking whichever step gives at most `target` ticks.
+///
+/// Steps go from every second up to every year. Days restart from the 1st of every month and
+/// daily ticks sit at local midnight, moving with daylight saving time. Ranges of only a few
+/// seconds are ticked like numbers, every 0.5 s or 0.1 s.
+#[derive(Debug, Clone)]
+pub struct TimeLocator<Tz: TimeZone> {
+    pub timezone: Tz,
+    pub target: usize,
+}
+
+impl<Tz: TimeZone> TimeLocator<Tz> {
+    /// Ticks in `timezone`, aiming for at most 7 of them.
+    pub fn new(timezone: Tz) -> Self {
+        Self { timezone, target: 7 }
+    }
+}
+
+impl<Tz: TimeZone> Locator for TimeLocator<Tz> {
+    fn locate(&self, min: f64, max: f64) -> Vec<f64> {
+        let (min, max) = (min.min(max), min.max(max));
+        let target = self.target.max(2) as f64;
+        let span = max - min;
+
+        let (Some(start), Some(end)) = (local_time(&self.timezone, min), local_time(&self.timezone, max)) else {
+            return NiceLocator::default().locate(min, max);
+        };
+        // a few seconds is too short for calendar steps, so it's split up like any other number
+        if span <= target {
+            return NiceLocator { target: self.target }.locate(min, max);
+        }
+
+        let (unit, step) = STEPS
+            .into_iter()
+            .find(|(unit, step)| span / (unit.seconds() * *step as f64) <= target)
+            .unwrap_or_else(|| {
+                let years = nice_step(span / Unit::Year.seconds() / target).ceil().max(1.0);
+                (Unit::Year, years.min(u32::MAX as f64) as u32)
+            });
+
+        // steps are taken in local time, so that "every day" means every midnight even when
+        // a day is 23 or 25 hours long
+        let mut time = round_down(start, unit, step);
+        let mut ticks = vec![];
+        while ticks.len() < MAX_TICKS {
+            // times skipped by daylight saving don't get a tick
+            if let Some(tick) = self.timezone.from_local_datetime(&time).earliest() {
+                ticks.push(timestamp(&tick));
+            }
+            if time >= end {
+                break;
+            }
+            match step_forward(time, unit, step) {
+                Some(next) => time = next,
+                None => break,
+            }
+        }
+
+        ticks
+    }
+}
+
+/// Labels timestamps as dates and times in `timezone`.
+///
+/// Labels show as much of the date and time as the spacing of the ticks needs, from
+/// `"14:30:05"` to `"Mar 2024"`, with daily ticks written like `"Mar 14"`. Hourly ticks at
+/// midnight show the date instead of `"00:00"`, so that days are easy to tell apart.
+#[derive(Debug, Clone)]
+pub struct TimeFormatter<Tz: TimeZone> {
+    pub timezone: Tz,
+    /// A `strftime`-style format, such as `"%Y-%m-%d"`, used for every label instead of picking one
+    /// from the spacing. Labels that can't be written with it fall back to the timestamp.
+    pub format: Option<String>,
+}
+
+impl<Tz: TimeZone> TimeFormatter<Tz> {
+    /// Labels in `timezone`, picking the format from the spacing of the ticks.
+    pub fn new(timezone: Tz) -> Self {
+        Self { timezone, format: None }
+    }
+
+    /// Labels every tick in `timezone` with a `strftime`-style format.
+    pub fn with_format(timezone: Tz, format: &str) -> Self {
+        Self { timezone, format: Some(format.to_string()) }
+    }
+}
+
+impl<Tz: TimeZone> Formatter for TimeFormatter<Tz>
+where
+    Tz::Offset: Display,
+{
+    fn format(&self, ticks: &[f64]) -> Vec<String> {
+        let spacing = ticks.windows(2).map(|pair| pair[1] - pair[0]).fold(f64::INFINITY, f64::min);
+
+        ticks
+            .iter()
+            .map(|tick| {
+                let written = date_time(&self.timezone, *tick).and_then(|time| {
+                    let format = self.format.as_deref().unwrap_or_else(|| pick_format(&time, spacing));
+                    // a format with a mistake in it, like an unknown specifier, can't be written
+                    let mut label = String::new();
+                    write!(label, "{}", time.format(format)).ok().map(|_| label)
+                });
+                written.unwrap_or_else(|| format_ticks(&[*tick]).remove(0))
+            })
+            .collect()
+    }
+}
+
+/// The format for a tick, given the smallest gap in seconds between it and its neighbours.
+fn pick_format<Tz: TimeZone>(time: &DateTime<Tz>, spacing: f64) -> &'static str {
+    let midnight = time.num_seconds_from_midnight() == 0 && time.nanosecond() == 0;
+
+    if spacing < 1.0 {
+        "%H:%M:%S%.3f"
+    } else if spacing < Unit::Minute.seconds() {
+        "%H:%M:%S"
+    // days that lose an hour to daylight saving are a little short
+    } else if spacing < Unit::Day.seconds() * 0.9 {
+        if midnight { "%b %-d" } else { "%H:%M" }
+    } else if spacing < Unit::Month.seconds() * 0.9 {
+        "%b %-d"
+    } else if spacing < Unit::Year.seconds() * 0.9 {
+        "%b %Y"
+    } else {
+        "%Y"
+    }
+}
+
+/// The last round time at or before `time`, for ticks stepping by `step` units.
+fn round_down(time: NaiveDateTime, unit: Unit, step: u32) -> NaiveDateTime {
+    let date = time.date();
+    let round = |value: u32| value - value % step;
+
+    let rounded = match unit {
+        Unit::Second => date.and_hms_opt(time.hour(), time.minute(), round(time.second())),
+        Unit::Minute => date.and_hms_opt(time.hour(), round(time.minute()), 0),
+        Unit::Hour => date.and_hms_opt(round(time.hour()), 0, 0),
+        // days and months count from 1, so they're rounded down to 1, 1 + step, 1 + 2 * step...
+        Unit::Day => date.with_day(round(date.day() - 1) + 1).and_then(|date| date.and_hms_opt(0, 0, 0)),
+        Unit::Month => NaiveDate::from_ymd_opt(date.year(), round(date.month() - 1) + 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0)),
+        Unit::Year => NaiveDate::from_ymd_opt(date.year() - date.year().rem_euclid(step as i32), 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0)),
+    };
+
+    rounded.unwrap_or(time)
+}
+
+/// The next round time after `time`, or `None` once past the last date chrono can hold.
+fn step_forward(time: NaiveDateTime, unit: Unit, step: u32) -> Option<NaiveDateTime> {
+    match unit {
+        Unit::Second | Unit::Minute | Unit::Hour => {
+            time.checked_add_signed(TimeDelta::try_seconds(unit.seconds() as i64 * step as i64)?)
+        }
+        Unit::Day => {
+            // days start again from the 1st when the month changes, so that steps line up with months
+            let next = time.date().checked_add_days(Days::new(step as u64))?;
+            let next = if next.month() == time.month() { next } else { next.with_day(1)? };
+            next.and_hms_opt(0, 0, 0)
+        }
+        Unit::Month => time.date().checked_add_months(Months::new(step))?.and_hms_opt(0, 0, 0),
+        Unit::Year => NaiveDate::from_ymd_opt(time.year().checked_add(step as i32)?, 1, 1)?.and_hms_opt(0, 0, 0),
+    }
+}
+
+/// The date and time `timestamp` seconds after the Unix epoch, or `None` if chrono can't hold it.
+fn date_time<Tz: TimeZone>(timezone: &Tz, timestamp: f64) -> Option<DateTime<Tz>> {
+    if !timestamp.is_finite() || timestamp.abs() >= i64::MAX as f64 {
+        return None;
+    }
+    let seconds = timestamp.floor();
+    let nanos = ((timestamp - seconds) * 1e9).round().min(999_999_999.0) as u32;
+
+    timezone.timestamp_opt(seconds as i64, nanos).single()
+}
+
+/// The wall clock time in `timezone`, `timestamp` seconds after the Unix epoch.
+fn local_time<Tz: TimeZone>(timezone: &Tz, timestamp: f64) -> Option<NaiveDateTime> {
+    date_time(timezone, timestamp).map(|time| time.naive_local())
+}
+
+/// Seconds since the Unix epoch, the way timestamps are plotted.
+pub(crate) fn timestamp<Tz: TimeZone>(time: &DateTime<Tz>) -> f64 {
+    time.timestamp() as f64 + time.timestamp_subsec_nanos() as f64 / 1e9
+}
@@ -0,0 +1,166 @@
+//! The values charts can be loaded with
+//!
+//! Every chart takes its data as an iterator of values implementing [`Numeric`], which covers every
+//! integer and floating point type, along with references to them. So a `Vec<(f64, u64)>`, a slice of
+//! `(i32, f32)` pairs or a `Range<usize>` can all be loaded without converting them first.
+//!
+//! Gaps in the data can be loaded as `None`, and along with NaN and infinite values, are dealt with the way
+//! a chart's [`MissingValues`] policy says.
+//!
+//! ```rust no_run
+//! use ferrischart::{charts::{Histogram, LineGraph}, data::MissingValues};
+//!
+//! # fn example() -> ferrischart::error::ChartResult<()> {
+//! let requests: Vec<(f64, u64)> = vec![(0.5, 120), (1.0, 340), (1.5, 290)];
+//! LineGraph::build().load_data(&requests).draw("requests.png")?;
+//!
+//! let seconds = [0.0, 1.0, 2.0];
+//! let errors: Vec<u64> = vec![3, 0, 7];
+//! LineGraph::build().load_columns(seconds, &errors).draw("errors.png")?;
+//!
+//! let latencies: &[f64] = &[1.2, 3.4, 2.2];
+//! Histogram::build().load_data(latencies).draw("latency.png")?;
+//!
+//! let readings = vec![(0, Some(21.5)), (1, None), (2, Some(22.1)), (3, Some(21.9))];
+//! let graph = LineGraph::build().load_data(readings).set_missing_values(MissingValues::Break);
+//! println!("{} readings were missing", graph.dropped_points());
+//! graph.draw("temperature.png")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{ChartErrors, ChartResult};
+
+/// A number a chart can be loaded with.
+///
+/// Integers too large to be represented exactly, such as `u64`s above 2⁵³, are rounded to the nearest value
+/// that can be.
+pub trait Numeric: Copy {
+    /// The value as a double precision float.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric {
+    ($($number:ty),*) => {
+        $(
+            impl Numeric for $number {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T: Numeric> Numeric for &T {
+    fn to_f64(self) -> f64 {
+        (*self).to_f64()
+    }
+}
+
+/// A missing value, which is treated the same as NaN. How charts deal with it is picked by [`MissingValues`].
+impl<T: Numeric> Numeric for Option<T> {
+    fn to_f64(self) -> f64 {
+        self.map_or(f64::NAN, Numeric::to_f64)
+    }
+}
+
+/// A point in time, plotted as the number of seconds since the Unix epoch. Needs the `chrono` feature.
+///
+/// Label the axis with [`TimeLocator`](crate::charts::time::TimeLocator) and
+/// [`TimeFormatter`](crate::charts::time::TimeFormatter) to show it as dates and times.
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> Numeric for chrono::DateTime<Tz>
+where
+    Self: Copy,
+{
+    fn to_f64(self) -> f64 {
+        crate::charts::time::timestamp(&self)
+    }
+}
+
+/// A date and time without a timezone, which is taken to be in UTC. Needs the `chrono` feature.
+#[cfg(feature = "chrono")]
+impl Numeric for chrono::NaiveDateTime {
+    fn to_f64(self) -> f64 {
+        self.and_utc().to_f64()
+    }
+}
+
+/// A date, plotted at midnight UTC. Needs the `chrono` feature.
+#[cfg(feature = "chrono")]
+impl Numeric for chrono::NaiveDate {
+    fn to_f64(self) -> f64 {
+        self.and_time(chrono::NaiveTime::MIN).to_f64()
+    }
+}
+
+/// An `(x, y)` pair a chart can be loaded with. The x and y values can be different types.
+pub trait IntoPoint {
+    /// The pair as double precision floats.
+    fn into_point(self) -> (f64, f64);
+}
+
+impl<X: Numeric, Y: Numeric> IntoPoint for (X, Y) {
+    fn into_point(self) -> (f64, f64) {
+        (self.0.to_f64(), self.1.to_f64())
+    }
+}
+
+impl<X: Numeric, Y: Numeric> IntoPoint for &(X, Y) {
+    fn into_point(self) -> (f64, f64) {
+        (self.0.to_f64(), self.1.to_f64())
+    }
+}
+
+/// What a chart does with values that are missing (`None`), NaN or infinite.
+///
+/// Every chart can say how many of its loaded points it won't draw, with its `dropped_points` method.
+/// That counts every point left out for any reason: values that are missing, NaN or infinite, values an
+/// axis' scale can't show (such as 0 on a log scale), and pie wedges whose value isn't positive, so they
+/// get no share of the pie. A histogram's bins always cover every finite sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingValues {
+    /// Leaves them out, drawing the rest of the data as if they weren't there.
+    #[default]
+    Skip,
+    /// Leaves them out, and breaks lines in two where they were, so that gaps in the data are gaps in the line.
+    /// Charts without lines treat this the same as [`MissingValues::Skip`].
+    Break,
+    /// Refuses to draw the chart, failing with [`ChartErrors::NonFiniteValue`] instead.
+    Error,
+}
+
+/// Adds the method that picks what a chart does with missing values to a chart's `impl` block.
+/// The chart needs a `missing_values: MissingValues` field.
+macro_rules! missing_values_methods {
+    () => {
+        /// Sets what happens to values that are missing, NaN or infinite.
+        /// Defaults to [`MissingValues::Skip`](crate::data::MissingValues::Skip), which leaves them out.
+        /// [`MissingValues::Break`](crate::data::MissingValues::Break) also leaves a gap in lines where they were.
+        pub fn set_missing_values(mut self, missing_values: $crate::data::MissingValues) -> Self {
+            self.missing_values = missing_values;
+
+            self
+        }
+    };
+}
+
+pub(crate) use missing_values_methods;
+
+impl MissingValues {
+    /// Fails for the first value that isn't finite, if that's what the policy asks for.
+    /// `finite` says whether each value of the series is finite.
+    pub(crate) fn check(self, series: &str, finite: impl IntoIterator<Item = bool>) -> ChartResult<()> {
+        if self != MissingValues::Error {
+            return Ok(());
+        }
+
+        match finite.into_iter().position(|finite| !finite) {
+            Some(index) => Err(ChartErrors::NonFiniteValue { series: series.to_string(), index }),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,16 +1,105 @@
 //! Holds the crate's `Result` type and error enum
 
+use std::{error::Error, fmt};
+
 use image::ImageError;
 
+/// Everything that can go wrong while building, drawing or saving a chart.
+///
+/// Problems with a chart's data are found when it's drawn, rather than when the data is loaded,
+/// so that every setter can still be chained.
 #[derive(Debug)]
 pub enum ChartErrors {
-    ImageError,
+    /// The chart was drawn without any data to plot.
+    EmptyData,
+    /// A value was missing, NaN or infinite, and the chart was asked not to leave it out.
+    /// See [`MissingValues`](crate::data::MissingValues).
+    NonFiniteValue {
+        /// The name of the series the value belongs to, which is empty for unnamed series.
+        series: String,
+        /// The position of the value within its series.
+        index: usize,
+    },
+    /// A setting describes a range or size that can't be drawn, such as bins with a width of 0.
+    InvalidRange(String),
+    /// A font couldn't be parsed. Holds the name it was being registered under.
+    FontLoad(String),
+    /// A file couldn't be read or written.
+    Io(std::io::Error),
+    /// An image couldn't be encoded.
+    Encoding(ImageError),
+    /// An image was asked for in a format that can't be written, such as from an unknown file extension.
+    UnsupportedFormat(String),
+    /// A theme couldn't be read or written.
+    Theme(Box<dyn Error + Send + Sync>),
 }
 
 pub type ChartResult<T> = Result<T, ChartErrors>;
 
+impl fmt::Display for ChartErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartErrors::EmptyData => write!(f, "the chart has no data to plot"),
+            ChartErrors::NonFiniteValue { series, index } if series.is_empty() => {
+                write!(f, "value {index} is not a finite number")
+            }
+            ChartErrors::NonFiniteValue { series, index } => {
+                write!(f, "value {index} of series \"{series}\" is not a finite number")
+            }
+            ChartErrors::InvalidRange(reason) => write!(f, "invalid range: {reason}"),
+            ChartErrors::FontLoad(name) => write!(f, "the font \"{name}\" couldn't be parsed"),
+            ChartErrors::Io(error) => write!(f, "I/O error: {error}"),
+            ChartErrors::Encoding(error) => write!(f, "couldn't encode the image: {error}"),
+            ChartErrors::UnsupportedFormat(format) => write!(f, "unsupported image format: {format}"),
+            ChartErrors::Theme(error) => write!(f, "invalid theme: {error}"),
+        }
+    }
+}
+
+impl Error for ChartErrors {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ChartErrors::Io(error) => Some(error),
+            ChartErrors::Encoding(error) => Some(error),
+            ChartErrors::Theme(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
 impl From<ImageError> for ChartErrors {
-    fn from(_: ImageError) -> Self {
-        ChartErrors::ImageError
+    fn from(error: ImageError) -> Self {
+        match error {
+            ImageError::IoError(error) => ChartErrors::Io(error),
+            ImageError::Unsupported(error) => ChartErrors::UnsupportedFormat(error.to_string()),
+            error => ChartErrors::Encoding(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for ChartErrors {
+    fn from(error: std::io::Error) -> Self {
+        ChartErrors::Io(error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<toml::de::Error> for ChartErrors {
+    fn from(error: toml::de::Error) -> Self {
+        ChartErrors::Theme(Box::new(error))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<toml::ser::Error> for ChartErrors {
+    fn from(error: toml::ser::Error) -> Self {
+        ChartErrors::Theme(Box::new(error))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for ChartErrors {
+    fn from(error: serde_json::Error) -> Self {
+        ChartErrors::Theme(Box::new(error))
     }
 }
@@ -0,0 +1,318 @@
+//! Fonts that chart text can be drawn in
+//!
+//! ferrischart bundles DejaVu Sans, which every chart uses by default, along with Roboto in a regular
+//! and a black (extra bold) weight. Any other TrueType or OpenType font can be added to the
+//! [`FontRegistry`] and then picked by name.
+//!
+//! ```rust no_run
+//! use ferrischart::{charts::ScatterGraph, font::{FontFamily, FontRegistry}};
+//!
+//! # fn example() -> ferrischart::error::ChartResult<()> {
+//! let brand = FontRegistry::register_file("Acme Sans", "fonts/AcmeSans.otf")?;
+//! ScatterGraph::build()
+//!     .set_title("Quarterly Revenue")
+//!     .set_font(brand)
+//!     .set_title_font(FontFamily::RobotoBlack)
+//!     .load_data(vec![(1.0, 2.0), (2.0, 4.0)])
+//!     .draw("revenue.png")?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Cargo features
+//! The bundled fonts are embedded into the binary by the `font-dejavu` and `font-roboto` features, which
+//! are both on by default. Turning either off makes the binary smaller, and its fonts are then looked up
+//! in the registry by name instead, such as ones found by [`FontRegistry::load_system_fonts`] with the
+//! `font-system` feature. Text with no font to draw it in at all is measured by an estimate, which
+//! is still enough for SVG documents, but is left out of bitmaps.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, OnceLock, RwLock}
+};
+#[cfg(feature = "font-system")]
+use std::path::PathBuf;
+
+use rusttype::Font;
+
+use crate::error::{ChartErrors, ChartResult};
+
+/// A font to draw text in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FontFamily {
+    /// The bundled DejaVu Sans, which covers most of Unicode. Needs the `font-dejavu` feature to be bundled.
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "dejavu_sans"))]
+    DejaVuSans,
+    /// The bundled Roboto, in its regular weight. Needs the `font-roboto` feature to be bundled.
+    Roboto,
+    /// The bundled Roboto, in its heaviest weight. Suits titles. Needs the `font-roboto` feature to be bundled.
+    RobotoBlack,
+    /// A font added to the [`FontRegistry`], by the name it was registered under.
+    /// Text is drawn in DejaVu Sans if nothing has been registered under the name.
+    Custom(String),
+}
+
+impl FontFamily {
+    /// The name the font goes by, which SVG documents ask for it by.
+    pub fn name(&self) -> &str {
+        match self {
+            FontFamily::DejaVuSans => "DejaVu Sans",
+            FontFamily::Roboto | FontFamily::RobotoBlack => "Roboto",
+            FontFamily::Custom(name) => name,
+        }
+    }
+
+    /// How heavy the font is, from 100 (thin) to 900 (black), with 400 being regular.
+    /// Fonts in the registry are assumed to be regular.
+    pub fn weight(&self) -> u16 {
+        match self {
+            FontFamily::RobotoBlack => 900,
+            _ => 400,
+        }
+    }
+
+    /// The parsed font, which is only parsed the first time it's needed.
+    /// Fonts that aren't available fall back to whichever font is, and `None` means there isn't one at all.
+    pub(crate) fn font(&self) -> Option<Arc<Font<'static>>> {
+        let font = match self {
+            FontFamily::DejaVuSans => dejavu_sans(),
+            FontFamily::Roboto => roboto(),
+            FontFamily::RobotoBlack => roboto_black(),
+            FontFamily::Custom(name) => FontRegistry::get(name),
+        };
+
+        font.or_else(|| dejavu_sans().or_else(roboto).or_else(FontRegistry::any))
+    }
+}
+
+#[cfg(feature = "font-dejavu")]
+fn dejavu_sans() -> Option<Arc<Font<'static>>> {
+    static FONT: OnceLock<Arc<Font<'static>>> = OnceLock::new();
+    Some(bundled(&FONT, include_bytes!("assets/DejaVuSans.ttf")))
+}
+
+#[cfg(not(feature = "font-dejavu"))]
+fn dejavu_sans() -> Option<Arc<Font<'static>>> {
+    FontRegistry::get("DejaVu Sans")
+}
+
+#[cfg(feature = "font-roboto")]
+fn roboto() -> Option<Arc<Font<'static>>> {
+    static FONT: OnceLock<Arc<Font<'static>>> = OnceLock::new();
+    Some(bundled(&FONT, include_bytes!("assets/Roboto-Regular.ttf")))
+}
+
+#[cfg(not(feature = "font-roboto"))]
+fn roboto() -> Option<Arc<Font<'static>>> {
+    FontRegistry::get("Roboto")
+}
+
+#[cfg(feature = "font-roboto")]
+fn roboto_black() -> Option<Arc<Font<'static>>> {
+    static FONT: OnceLock<Arc<Font<'static>>> = OnceLock::new();
+    Some(bundled(&FONT, include_bytes!("assets/Roboto-Black.ttf")))
+}
+
+#[cfg(not(feature = "font-roboto"))]
+fn roboto_black() -> Option<Arc<Font<'static>>> {
+    FontRegistry::get("Roboto Black")
+}
+
+/// Parses a bundled font the first time it's asked for.
+#[cfg(any(feature = "font-dejavu", feature = "font-roboto"))]
+fn bundled(cell: &'static OnceLock<Arc<Font<'static>>>, data: &'static [u8]) -> Arc<Font<'static>> {
+    // the bundled fonts are known to parse, so there's nothing to go wrong here
+    cell.get_or_init(|| Arc::new(Font::try_from_bytes(data).expect("bundled fonts are valid")))
+        .clone()
+}
+
+/// A font in the registry, which fonts found on the machine are only parsed into once they're used.
+enum Entry {
+    Parsed(Arc<Font<'static>>),
+    #[cfg(feature = "font-system")]
+    File(PathBuf),
+}
+
+/// The fonts added by name, shared by every chart in the program.
+///
+/// Fonts are parsed once, when they're registered, and drawn with from then on by using the
+/// [`FontFamily`] registering them gives back. Registering a font under a name that's already
+/// taken replaces the font.
+pub struct FontRegistry;
+
+impl FontRegistry {
+    fn fonts() -> &'static RwLock<HashMap<String, Entry>> {
+        static FONTS: OnceLock<RwLock<HashMap<String, Entry>>> = OnceLock::new();
+        FONTS.get_or_init(|| RwLock::new(HashMap::new()))
+    }
+
+    /// Adds a TrueType or OpenType font from its bytes, such as ones embedded with `include_bytes!`.
+    pub fn register_bytes(name: &str, data: Vec<u8>) -> ChartResult<FontFamily> {
+        let font = Font::try_from_vec(data).ok_or_else(|| ChartErrors::FontLoad(name.to_string()))?;
+        // a poisoned lock only means another thread panicked mid-insert, which leaves the map intact
+        let mut fonts = Self::fonts().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        fonts.insert(name.to_string(), Entry::Parsed(Arc::new(font)));
+
+        Ok(FontFamily::Custom(name.to_string()))
+    }
+
+    /// Adds a TrueType (`.ttf`) or OpenType (`.otf`) font from a file.
+    pub fn register_file<P: AsRef<Path>>(name: &str, path: P) -> ChartResult<FontFamily> {
+        let data = std::fs::read(path)?;
+        Self::register_bytes(name, data)
+    }
+
+    /// Whether a font has been registered under `name`.
+    pub fn contains(name: &str) -> bool {
+        let fonts = Self::fonts().read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        fonts.contains_key(name)
+    }
+
+    /// The names of every registered font, in alphabetical order.
+    pub fn names() -> Vec<String> {
+        let fonts = Self::fonts().read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut names: Vec<String> = fonts.keys().cloned().collect();
+        names.sort();
+
+        names
+    }
+
+    /// The font registered under `name`, parsing it first if it was found on the machine.
+    fn get(name: &str) -> Option<Arc<Font<'static>>> {
+        let fonts = Self::fonts().read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match fonts.get(name)? {
+            Entry::Parsed(font) => Some(font.clone()),
+            #[cfg(feature = "font-system")]
+            Entry::File(path) => {
+                let path = path.clone();
+                drop(fonts);
+                Self::parse_file(name, &path)
+            }
+        }
+    }
+
+    /// The first usable font in alphabetical order, for text whose font isn't available.
+    fn any() -> Option<Arc<Font<'static>>> {
+        Self::names().iter().find_map(|name| Self::get(name))
+    }
+}
+
+#[cfg(feature = "font-system")]
+impl FontRegistry {
+    /// Finds the fonts installed in the standard font directories on Linux (`/usr/share/fonts`,
+    /// `/usr/local/share/fonts`, `~/.local/share/fonts` and `~/.fonts`), and registers each by its full
+    /// name, such as `"DejaVu Sans Bold"`. Returns how many fonts were found.
+    ///
+    /// Only the names of the fonts are read until they're drawn with. Fonts already registered under the
+    /// same name are kept, so fonts registered by hand always win.
+    pub fn load_system_fonts() -> usize {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".local/share")));
+
+        let mut directories = vec![PathBuf::from("/usr/share/fonts"), PathBuf::from("/usr/local/share/fonts")];
+        directories.extend(data_home.map(|data_home| data_home.join("fonts")));
+        directories.extend(home.map(|home| home.join(".fonts")));
+
+        directories.iter().map(Self::load_font_directory).sum()
+    }
+
+    /// Parses a font that was found on the machine, in place of its path.
+    fn parse_file(name: &str, path: &Path) -> Option<Arc<Font<'static>>> {
+        // fonts that can't be read are forgotten about, rather than being read again every time
+        let font = std::fs::read(path).ok().and_then(Font::try_from_vec).map(Arc::new);
+        let mut fonts = Self::fonts().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match &font {
+            Some(font) => fonts.insert(name.to_string(), Entry::Parsed(font.clone())),
+            None => fonts.remove(name),
+        };
+
+        font
+    }
+
+    /// Registers every font in `directory` and the directories inside of it, the same way
+    /// [`load_system_fonts`](FontRegistry::load_system_fonts) does. Returns how many fonts were found.
+    pub fn load_font_directory<P: AsRef<Path>>(directory: P) -> usize {
+        let mut found = vec![];
+        find_font_files(directory.as_ref(), 0, &mut found);
+
+        let mut fonts = Self::fonts().write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut count = 0;
+        for (name, path) in found {
+            fonts.entry(name).or_insert(Entry::File(path));
+            count += 1;
+        }
+
+        count
+    }
+}
+
+/// Collects the name and path of every font file under `directory`.
+#[cfg(feature = "font-system")]
+fn find_font_files(directory: &Path, depth: usize, found: &mut Vec<(String, PathBuf)>) {
+    // font directories are only ever a few levels deep, and this stops symlinks from going round in circles
+    const MAX_DEPTH: usize = 8;
+    let Ok(entries) = std::fs::read_dir(directory) else { return };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            if depth < MAX_DEPTH {
+                find_font_files(&path, depth + 1, found);
+            }
+            continue;
+        }
+
+        let is_font = path
+            .extension()
+            .map(|extension| extension.eq_ignore_ascii_case("ttf") || extension.eq_ignore_ascii_case("otf"))
+            .unwrap_or(false);
+        if let Some(name) = is_font.then(|| font_name(&path)).flatten() {
+            found.push((name, path));
+        }
+    }
+}
+
+/// The full name a font file gives itself, such as `"DejaVu Sans Bold"`.
+///
+/// Only the table directory at the start of the file and the name table are read, since font files
+/// can be megabytes long and a machine can have thousands of them.
+#[cfg(feature = "font-system")]
+fn font_name(path: &Path) -> Option<String> {
+    use std::{fs::File, io::{Read, Seek, SeekFrom}};
+    use ttf_parser::{name::Table, name_id};
+
+    // name tables are a few kilobytes, so anything claiming to be much bigger isn't one
+    const MAX_NAME_TABLE: u32 = 1 << 20;
+    let be_u32 = |bytes: &[u8]| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    // the file starts with how many tables it has, followed by a 16 byte record for each
+    // of them holding its tag, checksum, offset and length
+    let mut file = File::open(path).ok()?;
+    let mut header = [0; 12];
+    file.read_exact(&mut header).ok()?;
+    let table_count = u16::from_be_bytes([header[4], header[5]]) as usize;
+    let mut records = vec![0; table_count * 16];
+    file.read_exact(&mut records).ok()?;
+    let record = records.chunks_exact(16).find(|record| &record[..4] == b"name")?;
+    let (offset, length) = (be_u32(&record[8..12]), be_u32(&record[12..16]));
+    if length > MAX_NAME_TABLE {
+        return None;
+    }
+
+    let mut data = vec![0; length as usize];
+    file.seek(SeekFrom::Start(offset as u64)).ok()?;
+    file.read_exact(&mut data).ok()?;
+    let table = Table::parse(&data)?;
+    let name = |id: u16| {
+        table.names
+            .into_iter()
+            .filter(|name| name.name_id == id)
+            .find_map(|name| name.to_string())
+    };
+
+    name(name_id::FULL_NAME).or_else(|| name(name_id::FAMILY))
+}
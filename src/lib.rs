@@ -33,18 +33,47 @@
 //! The following code should generate a graph that looks something like this (keep in mind, your data positions may differ)
 //! ![example graph](https://raw.githubusercontent.com/justanotherbyte/ferrischart/main/tests/scatter.png)
 
+pub mod backend;
 pub mod charts;
+pub mod data;
 pub mod error;
+pub mod font;
 pub mod math;
 
+pub use image;
+
 #[cfg(test)]
 mod tests {
+    use std::io::Cursor;
+
+    use image::{ImageFormat, Rgba};
     use rand::Rng;
 
     use crate::{
-        charts::ScatterGraph,
-        error::ChartResult,
-        math::distance::euclidean_distance
+        backend::{Backend, SvgBackend},
+        charts::{
+            axes::{GridLines, Spines, TickDirection},
+            bar::Orientation,
+            category::CategoryOrder,
+            histogram::{Bins, HistogramStyle},
+            marker::Marker,
+            palette::Palette,
+            scale::{AxisScale, Scale},
+            ticks::{
+                format_ticks, FixedLocator, Formatter, Locator, LogLocator, MaxNLocator, MultipleLocator,
+                NiceLocator, PowerFormatter, SymLogLocator
+            },
+            theme::Theme,
+            BarGraph, Histogram, LineGraph, Margins, PieChart, ScatterGraph
+        },
+        data::MissingValues,
+        error::{ChartErrors, ChartResult},
+        font::{FontFamily, FontRegistry},
+        math::{
+            binning::{bin_counts, bin_edges, freedman_diaconis, sturges},
+            distance::euclidean_distance,
+            statistics::{mean, quantile}
+        }
     };
 
     #[test]
@@ -56,15 +85,883 @@ mod tests {
             let y = rng.gen_range(1.0..7.0);
             data.push((x, y));
         }
-        ScatterGraph::build()
+        let image = ScatterGraph::build()
             .set_title("GCSE vs IB Grades")
             .set_axis_text("GCSE Grades", "IB Grades")
             .load_data(data)
-            .draw("tests/scatter.png")?;
+            .render()?;
+
+        assert_eq!(image.dimensions(), (500, 500));
+        // the background is white and the axes meet in black at the bottom-left of the plotting area
+        assert_eq!(*image.get_pixel(0, 0), Rgba([255, 255, 255, 255]));
+        assert_eq!(*image.get_pixel(50, 450), Rgba([0, 0, 0, 255]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn line_builder_series() -> ChartResult<()> {
+        let mut rng = rand::thread_rng();
+        let mut first = vec![];
+        let mut second = vec![];
+        for day in 0..10 {
+            first.push((day as f32, rng.gen_range(1.0..5.0)));
+            second.push((day as f32, rng.gen_range(3.0..8.0)));
+        }
+        let image = LineGraph::build()
+            .set_title("Daily Temperatures")
+            .set_axis_text("Day", "Temperature")
+            .add_series("London", first)
+            .add_series("Paris", second)
+            .render()?;
+        assert_eq!(image.dimensions(), (500, 500));
+        assert_eq!(*image.get_pixel(0, 0), Rgba([255, 255, 255, 255]));
+        assert_eq!(*image.get_pixel(50, 450), Rgba([0, 0, 0, 255]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn numeric_inputs() -> ChartResult<()> {
+        // x and y can be different types, and borrowed or owned
+        // the series are unnamed so that the only circles drawn are the points
+        let counters: Vec<(f64, u64)> = vec![(0.5, 120), (1.0, 340), (1.5, 290)];
+        let mut backend = SvgBackend::new(500, 500);
+        ScatterGraph::build()
+            .add_series("", &counters)
+            .add_series("", counters.clone())
+            .add_columns("", 0..3usize, [1i64, -2, 3, 4])
+            .draw_to(&mut backend)?;
+        // the column of y values has one more value than the column of x values, which is left out
+        assert_eq!(backend.document().matches("<circle").count(), 9);
+
+        let samples: Vec<u8> = vec![3, 1, 4, 1, 5];
+        Histogram::build().load_data(samples.as_slice()).render()?;
+        BarGraph::build().load_data(vec![("Requests", 12u64), ("Errors", 3)]).render()?;
+        PieChart::build().load_data([("Hits", 0.9f64), ("Misses", 0.1)]).render()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn large_magnitudes() -> ChartResult<()> {
+        // nanoseconds since the epoch, a second apart, are far closer together than an f32 can tell apart
+        let start: u64 = 1_700_000_000_000_000_000;
+        let times: Vec<u64> = (0..5).map(|second| start + second * 1_000_000_000).collect();
+        let mut backend = SvgBackend::new(500, 500);
+        ScatterGraph::build()
+            .load_columns(&times, [1.0, 2.0, 3.0, 4.0, 5.0])
+            .draw_to(&mut backend)?;
+        let document = backend.document();
+        let mut xs: Vec<&str> = document
+            .lines()
+            .filter(|line| line.starts_with("<circle"))
+            .map(|line| line.split('"').nth(1).unwrap())
+            .collect();
+        xs.dedup();
+        assert_eq!(xs.len(), 5);
+
+        let scale = Scale::linear((start as f64, start as f64 + 4e9), (0.0, 400.0));
+        assert_eq!(scale.map(start as f64 + 1e9), 100.0);
+        assert_eq!(euclidean_distance((0.0f64, 0.0), (3.0, 4.0)), 5.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scatter_series_legend() -> ChartResult<()> {
+        // the top-right corner is full of points, so the legend has to go somewhere else
+        let crowded: Vec<(f32, f32)> = (0..900).map(|i| (5.0 + (i % 30) as f32 * 0.25, 5.0 + (i / 30) as f32 * 0.25)).collect();
+        let mut backend = SvgBackend::new(500, 500);
+        ScatterGraph::build()
+            .set_title("Cohorts")
+            .set_axis_text("Week", "Score")
+            .add_series("Crowded", crowded)
+            .set_series_color(Rgba([0, 0, 255, 255]))
+            .add_series("Sparse", vec![(0.0, 0.0), (1.0, 2.0)])
+            .set_series_marker(Marker::Square)
+            .set_labels(vec![0.0, 5.0, 10.0], vec![0.0, 5.0, 10.0])
+            .draw_to(&mut backend)?;
+        let document = backend.document();
+        assert!(document.contains("fill=\"#0000ff\""));
+
+        let legend_text = document.lines().find(|line| line.contains(">Crowded</text>")).unwrap();
+        let x: f32 = legend_text.split('"').nth(1).unwrap().parse().unwrap();
+        assert!(x < 250.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn scatter_point_styles() {
+        let points = || vec![(1.0, 1.0), (1.0, 1.0), (2.0, 3.0)];
+        let draw = |graph: ScatterGraph| {
+            let mut backend = SvgBackend::new(500, 500);
+            graph.set_title("Bubbles").set_axis_text("x", "y").draw_to(&mut backend).unwrap();
+            backend.document()
+        };
+
+        // every point gets its own size and colour
+        let document = draw(
+            ScatterGraph::build()
+                .add_series("", points())
+                .set_series_sizes(vec![2.0, 4.0, 8.0])
+                .set_series_colors(vec![Rgba([255, 0, 0, 255])])
+        );
+        assert_eq!(document.matches("<circle").count(), 3);
+        assert!(document.contains("r=\"8\""));
+        assert_eq!(document.matches("fill=\"#ff0000\"").count(), 1);
+
+        // counting duplicates is opt-in, and grows the first marker instead of drawing another
+        let document = draw(ScatterGraph::build().load_data(points()).set_count_duplicates(true));
+        assert_eq!(document.matches("<circle").count(), 2);
+        assert!(document.contains("r=\"4\""));
+    }
+
+    #[test]
+    fn translucent_markers() -> ChartResult<()> {
+        // two half see-through red markers on top of each other, in the middle of the plotting area
+        let build = || {
+            ScatterGraph::build()
+                .set_title("Overplotted")
+                .set_axis_text("x", "y")
+                .load_data(vec![(0.5, 15.0), (0.5, 15.0)])
+                .set_series_color(Rgba([255, 0, 0, 255]))
+                .set_series_opacity(0.5)
+                .set_series_marker_size(10.0)
+                .set_labels(vec![0.25, 0.75], vec![10.0, 20.0])
+        };
+
+        // each one lets half of what's underneath show through, so a quarter of the white is left
+        let image = build().render()?;
+        assert_eq!(*image.get_pixel(250, 250), Rgba([255, 63, 63, 255]));
+        // the edge of the circle is only partly covered, so it fades into the background
+        let edge = image.get_pixel(260, 250);
+        assert!(edge[1] > 63 && edge[1] < 255);
+
+        let mut backend = SvgBackend::new(500, 500);
+        build().draw_to(&mut backend)?;
+        assert!(backend.document().contains("fill=\"#ff0000\" fill-opacity=\"0.5\""));
+
+        // the arms of a see-through cross are one shape, so they're only painted once where they meet
+        for marker in [Marker::Plus, Marker::Cross] {
+            let image = ScatterGraph::build()
+                .load_data(vec![(0.5, 15.0)])
+                .set_series_color(Rgba([255, 0, 0, 255]))
+                .set_series_opacity(0.5)
+                .set_series_marker(marker)
+                .set_series_marker_size(10.0)
+                .set_labels(vec![0.25, 0.75], vec![10.0, 20.0])
+                .render()?;
+            let (center, arm) = match marker {
+                Marker::Plus => (image.get_pixel(250, 250), image.get_pixel(250, 255)),
+                _ => (image.get_pixel(250, 250), image.get_pixel(254, 254)),
+            };
+            assert_eq!(center, arm);
+            assert_eq!(*center, Rgba([255, 127, 127, 255]));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_and_spines() -> ChartResult<()> {
+        let build = || {
+            ScatterGraph::build()
+                .set_title("Grid")
+                .set_axis_text("x", "y")
+                .load_data(vec![(1.0, 1.0), (2.0, 4.0), (3.0, 9.0)])
+                .set_labels(vec![1.0, 2.0, 3.0], vec![0.0, 5.0, 10.0])
+        };
+
+        let lines = |graph: ScatterGraph| {
+            let mut backend = SvgBackend::new(500, 500);
+            graph.draw_to(&mut backend).unwrap();
+            backend.document().matches("<line").count()
+        };
+        let plain = lines(build());
+        // one grid line for each of the 3 y labels, all dashed
+        let mut backend = SvgBackend::new(500, 500);
+        build()
+            .set_y_grid(GridLines::default().dashed(vec![4.0, 2.0]))
+            .draw_to(&mut backend)?;
+        assert_eq!(backend.document().matches("<line").count(), plain + 3);
+        assert_eq!(backend.document().matches("stroke-dasharray").count(), 3);
+
+        // two more spines, and each tick drawn on both sides is still a single line
+        assert_eq!(lines(build().set_spines(Spines::all()).set_tick_direction(TickDirection::Both)), plain + 2);
+        // hiding the spines and ticks takes away both spines and all 6 ticks
+        assert_eq!(lines(build().set_spines(Spines::none()).set_tick_length(0.0)), plain - 8);
+
+        // a solid grid shows up in the bitmap between the points
+        let image = build().set_x_grid(GridLines::new(Rgba([0, 0, 255, 255]), 1.0)).render()?;
+        assert!((60..440).any(|y| image.get_pixel(250, y)[2] == 255 && image.get_pixel(250, y)[0] == 0));
+
+        // linear axes have minor ticks too, so a minor grid splits each step of 2 into 4
+        let image = ScatterGraph::build()
+            .load_data(vec![(0.0, 0.0), (10.0, 10.0)])
+            .set_y_minor_grid(GridLines::new(Rgba([0, 255, 0, 255]), 1.0))
+            .render()?;
+        let green = |y: u32| image.get_pixel(250, y)[1] > image.get_pixel(250, y)[0];
+        let lines = (1..500).filter(|y| green(*y) && !green(y - 1)).count();
+        assert_eq!(lines, 15);
+
+        Ok(())
+    }
+
+    #[test]
+    fn themes() -> ChartResult<()> {
+        let build = || {
+            ScatterGraph::build()
+                .set_title("Themed")
+                .set_axis_text("x", "y")
+                .load_data(vec![(1.0, 1.0), (2.0, 4.0), (3.0, 9.0)])
+        };
+
+        // the corner is always background, and the middle of the plotting area is too, away from the points
+        let image = build().set_theme(Theme::dark()).render()?;
+        assert_eq!(*image.get_pixel(1, 1), Theme::dark().background);
+        let image = build().set_theme(Theme::ggplot()).render()?;
+        assert_eq!(*image.get_pixel(1, 1), Rgba([255, 255, 255, 255]));
+        assert_eq!(*image.get_pixel(120, 120), Theme::ggplot().plot_background.unwrap());
+
+        // setters tweak the theme whether they're called before or after it, rather than being replaced by it
+        let mut backend = SvgBackend::new(500, 500);
+        build()
+            .set_theme(Theme::seaborn())
+            .set_palette(Palette::Custom(vec![Rgba([1, 2, 3, 255])]))
+            .draw_to(&mut backend)?;
+        assert_eq!(backend.document().matches("fill=\"#010203\"").count(), 3);
+        let palette_first = build()
+            .set_palette(Palette::Custom(vec![Rgba([1, 2, 3, 255])]))
+            .set_tick_length(0.0)
+            .set_theme(Theme::seaborn());
+        let theme_first = build()
+            .set_theme(Theme::seaborn())
+            .set_tick_length(0.0)
+            .set_palette(Palette::Custom(vec![Rgba([1, 2, 3, 255])]));
+        assert_eq!(palette_first.render()?, theme_first.render()?);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn theme_files() -> ChartResult<()> {
+        for theme in [Theme::default(), Theme::dark(), Theme::ggplot(), Theme::minimal()] {
+            assert_eq!(Theme::from_toml(&theme.to_toml()?)?, theme);
+            assert_eq!(Theme::from_json(&theme.to_json()?)?, theme);
+        }
+
+        // anything left out comes from the default theme
+        let branded = Theme::from_toml(
+            r##"
+            text_color = "#003057"
+            legend_border = "none"
+            palette = { custom = ["#003057", "#b3a369"] }
+            "##
+        )?;
+        assert_eq!(branded.text_color, Rgba([0, 48, 87, 255]));
+        assert_eq!(branded.legend_border, None);
+        assert_eq!(branded.palette.color(1), Rgba([179, 163, 105, 255]));
+        assert_eq!(branded.title_size, Theme::default().title_size);
+
+        assert!(Theme::from_json(r#"{ "background": "white" }"#).is_err());
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "font-dejavu", feature = "font-roboto"))]
+    #[test]
+    fn fonts() -> ChartResult<()> {
+        // the bundled fonts are different shapes, so the same text takes up a different amount of room
+        let backend = SvgBackend::new(100, 100);
+        let dejavu = backend.text_size("Ferris", &FontFamily::DejaVuSans, 20.0);
+        let roboto = backend.text_size("Ferris", &FontFamily::Roboto, 20.0);
+        assert_ne!(dejavu.0, roboto.0);
+
+        let roboto_bytes = include_bytes!("assets/Roboto-Regular.ttf").to_vec();
+        let brand = FontRegistry::register_bytes("Brand Sans", roboto_bytes)?;
+        assert!(FontRegistry::names().contains(&"Brand Sans".to_string()));
+        assert_eq!(backend.text_size("Ferris", &brand, 20.0), roboto);
+        assert!(FontRegistry::register_bytes("Broken", vec![1, 2, 3]).is_err());
+        // fonts nobody registered fall back to DejaVu Sans
+        assert_eq!(backend.text_size("Ferris", &FontFamily::Custom("Missing".to_string()), 20.0), dejavu);
+
+        let mut backend = SvgBackend::new(500, 500);
+        ScatterGraph::build()
+            .set_title("Fonts")
+            .set_axis_text("x", "y")
+            .set_font(brand)
+            .set_title_font(FontFamily::RobotoBlack)
+            .load_data(vec![(1.0, 2.0), (2.0, 3.0)])
+            .draw_to(&mut backend)?;
+        let document = backend.document();
+        assert!(document.contains("font-family=\"Roboto, sans-serif\""));
+        assert!(document.contains("font-weight=\"900\" fill=\"#000000\">Fonts</text>"));
+        assert!(document.contains("font-family=\"Brand Sans, sans-serif\""));
+        assert!(!document.contains("DejaVu Sans"));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "font-system")]
+    #[test]
+    fn system_fonts() {
+        // fonts are found in nested directories, and registered by the name inside of the font
+        let directory = std::env::temp_dir().join("ferrischart-fonts").join("truetype");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("Regular.ttf"), include_bytes!("assets/Roboto-Black.ttf")).unwrap();
+        std::fs::write(directory.join("notes.txt"), "not a font").unwrap();
+
+        assert_eq!(FontRegistry::load_font_directory(directory.parent().unwrap()), 1);
+        assert!(FontRegistry::contains("Roboto Black"));
+        let backend = SvgBackend::new(100, 100);
+        assert_eq!(
+            backend.text_size("Ferris", &FontFamily::Custom("Roboto Black".to_string()), 20.0),
+            backend.text_size("Ferris", &FontFamily::RobotoBlack, 20.0)
+        );
+    }
+
+    #[test]
+    fn palettes() -> ChartResult<()> {
+        // qualitative palettes start again once they run out, colormaps blend between their stops
+        assert_eq!(Palette::Tab10.color(10), Palette::Tab10.color(0));
+        assert_eq!(Palette::OkabeIto.color(1), Rgba([86, 180, 233, 255]));
+        assert_eq!(Palette::Magma.map(0.0), Rgba([0, 0, 4, 255]));
+        assert_eq!(Palette::Viridis.map_value(5.0, 0.0, 10.0), Rgba([35, 144, 140, 255]));
+
+        // the same chart renders to exactly the same image every time
+        let build = || {
+            PieChart::build()
+                .set_title("Browsers")
+                .load_data(vec![("Firefox", 3.0), ("Chrome", 6.0), ("Safari", 2.0)])
+                .set_palette(Palette::Set2)
+        };
+        assert_eq!(build().render()?, build().render()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bar_builder_grouped() -> ChartResult<()> {
+        let image = BarGraph::build()
+            .set_title("Fruit Sales")
+            .set_axis_text("Fruit", "Sales")
+            .add_series("Monday", vec![("Apples", 5.0), ("Pears", 3.0), ("Plums", 7.0)])
+            .add_series("Tuesday", vec![("Apples", 4.0), ("Pears", 6.0), ("Kiwis", 2.0)])
+            .render()?;
+        assert_eq!(image.dimensions(), (500, 500));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bar_builder_stacked_horizontal() -> ChartResult<()> {
+        let image = BarGraph::build()
+            .set_title("Fruit Sales")
+            .set_axis_text("Sales", "Fruit")
+            .set_orientation(Orientation::Horizontal)
+            .set_stacked(true)
+            .add_series("Monday", vec![("Apples", 5.0), ("Pears", 3.0), ("Plums", 7.0)])
+            .add_series("Tuesday", vec![("Apples", 4.0), ("Pears", 6.0), ("Plums", 2.0)])
+            .render()?;
+        assert_eq!(image.dimensions(), (500, 500));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bar_off_the_scale() -> ChartResult<()> {
+        // ticks far smaller than the data put the bar billions of pixels above the canvas,
+        // which still only fills the plotting area
+        let image = BarGraph::build()
+            .load_data(vec![("Requests", 1e9)])
+            .set_value_locator(FixedLocator(vec![0.0, 1e-6]))
+            .render()?;
+        assert_ne!(*image.get_pixel(250, 100), Rgba([255, 255, 255, 255]));
+
+        Histogram::build()
+            .load_data(vec![1e9, 2e9, 2e9])
+            .set_y_locator(FixedLocator(vec![0.0, 1e-6]))
+            .render()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn histogram_builder_bins() -> ChartResult<()> {
+        let mut rng = rand::thread_rng();
+        let samples: Vec<f32> = (0..500)
+            .map(|_| rng.gen_range(0.0..4.0) + rng.gen_range(0.0..4.0))
+            .collect();
+        Histogram::build()
+            .set_title("Sum of Two Dice")
+            .set_axis_text("Total", "Frequency")
+            .load_data(samples.clone())
+            .set_bins(Bins::FreedmanDiaconis)
+            .render()?;
+        Histogram::build()
+            .set_title("Sum of Two Dice")
+            .set_axis_text("Total", "Cumulative Density")
+            .load_data(samples)
+            .set_bins(Bins::Width(0.5))
+            .set_density(true)
+            .set_cumulative(true)
+            .set_style(HistogramStyle::Step)
+            .render()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn pie_builder_donut() -> ChartResult<()> {
+        let data = vec![
+            ("Rust", 45.0),
+            ("Python", 25.0),
+            ("Go", 15.0),
+            ("C", 10.0),
+            ("Zig", 3.0),
+            ("Haskell", 2.0),
+        ];
+        let white = Rgba([255, 255, 255, 255]);
+        let pie = PieChart::build()
+            .set_title("Favourite Languages")
+            .load_data(data.clone())
+            .set_explode("Rust", 0.1)
+            .render()?;
+        let donut = PieChart::build()
+            .set_title("Favourite Languages")
+            .load_data(data.clone())
+            .set_inner_radius(0.5)
+            .set_start_angle(0.0)
+            .render()?;
+        // wedges can be exploded before their data is loaded
+        let exploded_first = PieChart::build()
+            .set_title("Favourite Languages")
+            .set_explode("Rust", 0.1)
+            .load_data(data)
+            .render()?;
+        assert_eq!(exploded_first, pie);
+        // only the donut has its centre cut out
+        assert_ne!(*pie.get_pixel(250, 270), white);
+        assert_eq!(*donut.get_pixel(250, 270), white);
+
+        // a see-through wedge is read against the background behind it, so its label is white on a dark theme
+        let label = |theme: Theme| {
+            let mut backend = SvgBackend::new(500, 500);
+            PieChart::build()
+                .load_data(vec![("All", 1.0)])
+                .set_theme(theme)
+                .set_palette(Palette::Custom(vec![Rgba([255, 255, 255, 100])]))
+                .draw_to(&mut backend)
+                .unwrap();
+            backend.document()
+        };
+        assert!(label(Theme::default()).contains("fill=\"#000000\">100.0%</text>"));
+        assert!(label(Theme::dark()).contains("fill=\"#ffffff\">100.0%</text>"));
 
         Ok(())
     }
 
+    #[test]
+    fn size_margins_and_scale() -> ChartResult<()> {
+        let wide = ScatterGraph::build()
+            .set_size(800, 400)
+            .set_margins(Margins::new(40.0, 20.0, 60.0, 80.0))
+            .load_data(vec![(1.0, 1.0), (2.0, 2.0)])
+            .render()?;
+        assert_eq!(wide.dimensions(), (800, 400));
+        // the axes meet at the bottom-left of the plotting area, inside of the margins
+        assert_eq!(*wide.get_pixel(80, 340), Rgba([0, 0, 0, 255]));
+
+        // a scaled chart looks the same, just bigger
+        let printed = LineGraph::build()
+            .set_dpi(300.0)
+            .load_data(vec![(1.0, 1.0), (2.0, 4.0)])
+            .render()?;
+        assert_eq!(printed.dimensions(), (1500, 1500));
+        assert_eq!(*printed.get_pixel(150, 1350), Rgba([0, 0, 0, 255]));
+        assert_eq!(*printed.get_pixel(146, 1350), Rgba([255, 255, 255, 255]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_to_buffer() -> ChartResult<()> {
+        let mut buffer = Cursor::new(vec![]);
+        LineGraph::build()
+            .load_data(vec![(1.0, 1.0), (2.0, 4.0), (3.0, 9.0)])
+            .write_to(&mut buffer, ImageFormat::Png)?;
+
+        // every PNG starts with the same 8 byte signature
+        assert!(buffer.get_ref().starts_with(&[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]));
+
+        // PNGs keep a see-through background, JPEGs can't so it's painted over white
+        let see_through = || {
+            LineGraph::build()
+                .load_data(vec![(1.0, 1.0), (2.0, 4.0)])
+                .set_theme(Theme { background: Rgba([0, 0, 0, 0]), ..Theme::default() })
+        };
+        let mut png = Cursor::new(vec![]);
+        see_through().write_to(&mut png, ImageFormat::Png)?;
+        let png = image::load_from_memory(png.get_ref())?.into_rgba8();
+        assert_eq!(*png.get_pixel(0, 0), Rgba([0, 0, 0, 0]));
+        let mut jpeg = Cursor::new(vec![]);
+        see_through().write_to(&mut jpeg, ImageFormat::Jpeg)?;
+        let jpeg = image::load_from_memory(jpeg.get_ref())?.into_rgba8();
+        assert!(jpeg.get_pixel(0, 0).0.iter().all(|channel| *channel > 250));
+        // and SVGs only get the chart's own background, so they stay see-through too
+        let mut svg = SvgBackend::new(500, 500);
+        see_through().draw_to(&mut svg)?;
+        let document = svg.document();
+        assert!(document.contains("fill=\"#000000\" fill-opacity=\"0\""));
+        assert!(!document.contains("fill=\"#ffffff\""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bad_data_is_an_error() {
+        use std::error::Error;
+
+        // problems with the data are reported when drawing, instead of panicking
+        let empty: Vec<(f32, f32)> = vec![];
+        assert!(matches!(ScatterGraph::build().load_data(empty).render(), Err(ChartErrors::EmptyData)));
+        assert!(matches!(PieChart::build().render(), Err(ChartErrors::EmptyData)));
+        let nothing_to_share = PieChart::build().load_data([("Refunds", -3.0), ("Sales", 0.0)]).render();
+        assert!(matches!(nothing_to_share, Err(ChartErrors::EmptyData)));
+        let result = BarGraph::build()
+            .add_series("Sales", vec![("Q1", 1.0), ("Q2", f32::NAN)])
+            .set_missing_values(MissingValues::Error)
+            .render();
+        match result {
+            Err(error @ ChartErrors::NonFiniteValue { .. }) => {
+                assert_eq!(error.to_string(), "value 1 of series \"Sales\" is not a finite number");
+            }
+            _ => panic!("expected a non-finite value"),
+        }
+        let result = Histogram::build().load_data(vec![1.0, 2.0]).set_bins(Bins::Width(0.0)).render();
+        assert!(matches!(result, Err(ChartErrors::InvalidRange(_))));
+
+        // errors from saving keep what caused them
+        let graph = || LineGraph::build().load_data(vec![(1.0, 2.0), (2.0, 3.0)]);
+        assert!(matches!(graph().draw("graph.unknown"), Err(ChartErrors::UnsupportedFormat(_))));
+        let error = graph().draw_svg("no/such/directory/graph.svg").unwrap_err();
+        assert!(matches!(error, ChartErrors::Io(_)));
+        assert!(error.source().is_some());
+        let error = FontRegistry::register_bytes("Broken", vec![0; 16]).unwrap_err();
+        assert!(matches!(error, ChartErrors::FontLoad(name) if name == "Broken"));
+    }
+
+    #[test]
+    fn missing_values() -> ChartResult<()> {
+        let readings = || vec![(0, Some(1.0)), (1, Some(2.0)), (2, None), (3, Some(f64::INFINITY)), (4, Some(3.0)), (5, Some(2.0))];
+        let polylines = |missing_values| -> ChartResult<usize> {
+            let graph = LineGraph::build().load_data(readings()).set_missing_values(missing_values);
+            assert_eq!(graph.dropped_points(), 2);
+            let mut backend = SvgBackend::new(500, 500);
+            graph.draw_to(&mut backend)?;
+            Ok(backend.document().matches("<polyline").count())
+        };
+        // skipping joins up the points either side of the gap, breaking leaves it empty
+        assert_eq!(polylines(MissingValues::Skip)?, 1);
+        assert_eq!(polylines(MissingValues::Break)?, 2);
+        let error = polylines(MissingValues::Error).unwrap_err();
+        assert!(matches!(error, ChartErrors::NonFiniteValue { index: 2, .. }));
+
+        // unsorted points are reported by where they were given, and break the line where they were
+        let unsorted = || vec![(3.0, 3.0), (1.0, 1.0), (f64::NAN, 0.0), (5.0, 5.0), (5.5, f64::NAN), (6.0, 6.0)];
+        let error = LineGraph::build().load_data(unsorted()).set_missing_values(MissingValues::Error).render();
+        assert!(matches!(error, Err(ChartErrors::NonFiniteValue { index: 2, .. })));
+        let mut backend = SvgBackend::new(500, 500);
+        LineGraph::build()
+            .load_data(unsorted())
+            .set_missing_values(MissingValues::Break)
+            .draw_to(&mut backend)?;
+        // 1 and 3 are joined before the missing x, but the missing y at 5.5 leaves 5 and 6 on their own
+        let document = backend.document();
+        let polyline = document.lines().find(|line| line.starts_with("<polyline")).unwrap();
+        assert_eq!(polyline.split('"').nth(1).unwrap().split(' ').count(), 2);
+        assert_eq!(document.matches("<polyline").count(), 1);
+
+        let histogram = Histogram::build().load_data(vec![1.0, f64::NAN, 2.0, 2.5]);
+        assert_eq!(histogram.dropped_points(), 1);
+        histogram.render()?;
+        let nothing_left = Histogram::build().load_data([None::<f64>, None]).render();
+        assert!(matches!(nothing_left, Err(ChartErrors::EmptyData)));
+        // wedges that get no share of the pie aren't drawn either
+        let pie = PieChart::build().load_data(vec![("a", 1.0), ("b", 0.0), ("c", -2.0), ("d", f64::NAN)]);
+        assert_eq!(pie.dropped_points(), 3);
+
+        // ranges that can't be divided up sensibly still get labelled, rather than running forever
+        assert_eq!(MultipleLocator(1e-9).locate(0.0, 1e6), NiceLocator::default().locate(0.0, 1e6));
+        ScatterGraph::build().load_data(vec![(-1e308, 1.0), (1e308, 2.0)]).render()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn svg_output() -> ChartResult<()> {
+        let scatter_path = std::env::temp_dir().join("ferrischart-scatter.svg");
+        let scatter_path = scatter_path.to_str().unwrap();
+        ScatterGraph::build()
+            .set_title("Fish & Chips")
+            .set_axis_text("Fish", "Chips")
+            .load_data(vec![(1.0, 2.0), (2.0, 3.0), (3.0, 1.0)])
+            .draw(scatter_path)?;
+        let svg = std::fs::read_to_string(scatter_path).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert!(svg.contains(">Fish &amp; Chips</text>"));
+
+        // draw_svg writes SVG whatever the extension is
+        let pie_path = std::env::temp_dir().join("ferrischart-pie.txt");
+        let pie_path = pie_path.to_str().unwrap();
+        PieChart::build()
+            .load_data(vec![("A", 1.0), ("B", 2.0)])
+            .draw_svg(pie_path)?;
+        let svg = std::fs::read_to_string(pie_path).unwrap();
+        assert_eq!(svg.matches("<polygon").count(), 2);
+
+        Ok(())
+    }
+
+    /// Counts the primitives a chart draws, the same way a backend outside of ferrischart would receive them.
+    #[derive(Default)]
+    struct CountingBackend {
+        circles: usize,
+        texts: usize,
+        clipping: bool,
+        clipped_circles: usize
+    }
+
+    impl Backend for CountingBackend {
+        fn line(&mut self, _: (f32, f32), _: (f32, f32), _: f32, _: Rgba<u8>) {}
+        fn polyline(&mut self, _: &[(f32, f32)], _: f32, _: Rgba<u8>) {}
+        fn circle(&mut self, _: (f32, f32), _: f32, _: Rgba<u8>) {
+            self.circles += 1;
+            if self.clipping {
+                self.clipped_circles += 1;
+            }
+        }
+        fn fill_rect(&mut self, _: (f32, f32), _: (f32, f32), _: Rgba<u8>) {}
+        fn stroke_rect(&mut self, _: (f32, f32), _: (f32, f32), _: f32, _: Rgba<u8>) {}
+        fn polygon(&mut self, _: &[(f32, f32)], _: Rgba<u8>) {}
+        fn text(&mut self, _: (f32, f32), _: &str, _: &FontFamily, _: f32, _: Rgba<u8>) {
+            self.texts += 1;
+        }
+        fn vertical_text(&mut self, _: (f32, f32), _: &str, _: &FontFamily, _: f32, _: Rgba<u8>) {
+            self.texts += 1;
+        }
+        fn clip(&mut self, _: (f32, f32), _: (f32, f32)) {
+            self.clipping = true;
+        }
+        fn reset_clip(&mut self) {
+            self.clipping = false;
+        }
+    }
+
+    #[test]
+    fn custom_backend() -> ChartResult<()> {
+        let mut backend = CountingBackend::default();
+        ScatterGraph::build()
+            .set_title("Counting")
+            .set_axis_text("x", "y")
+            .load_data(vec![(1.0, 1.0), (2.0, 2.0), (2.0, 2.0)])
+            .set_count_duplicates(true)
+            .draw_to(&mut backend)?;
+
+        // counted duplicate points share a circle, and every point is kept inside of the axes
+        assert_eq!(backend.circles, 2);
+        assert_eq!(backend.clipped_circles, 2);
+        assert!(!backend.clipping);
+        // title, 2 axis texts, and 6 labels (1.0 to 2.0 in steps of 0.2) along each axis
+        assert_eq!(backend.texts, 15);
+
+        Ok(())
+    }
+
+    #[test]
+    fn custom_labels_place_points() -> ChartResult<()> {
+        // values that aren't a label, between labels that aren't whole numbers, are placed proportionally
+        let mut backend = SvgBackend::new(500, 500);
+        ScatterGraph::build()
+            .load_data(vec![(0.5, 15.0)])
+            .set_labels(vec![0.25, 0.75], vec![10.0, 20.0])
+            .draw_to(&mut backend)?;
+        assert!(backend.document().contains("<circle cx=\"250\" cy=\"250\""));
+
+        let scale = Scale::linear((10.0, 20.0), (450.0, 50.0));
+        assert_eq!(scale.map(25.0), -150.0);
+        assert_eq!(scale.invert(scale.map(12.5)), 12.5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn tick_locators() {
+        // thousands are labelled in thousands, and thousandths in thousandths
+        assert_eq!(NiceLocator::default().locate(0.0, 10000.0), vec![0.0, 2000.0, 4000.0, 6000.0, 8000.0, 10000.0]);
+        let small = NiceLocator::default().locate(0.001, 0.004);
+        assert_eq!(format_ticks(&small), vec!["0.0010", "0.0015", "0.0020", "0.0025", "0.0030", "0.0035", "0.0040"]);
+
+        assert_eq!(MaxNLocator(4).locate(0.0, 10.0), vec![0.0, 5.0, 10.0]);
+        assert_eq!(MultipleLocator(0.25).locate(0.1, 0.6), vec![0.0, 0.25, 0.5, 0.75]);
+        // minor ticks split steps starting with a 2 into 4, and the rest into 5
+        assert_eq!(NiceLocator::default().minor(0.0, 10.0)[..4], [0.5, 1.0, 1.5, 2.5]);
+        assert_eq!(MultipleLocator(5.0).minor(0.0, 5.0), vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(FixedLocator(vec![3.0, 1.0]).locate(0.0, 100.0), vec![1.0, 3.0]);
+        assert_eq!(format_ticks(&[-0.5, 0.0, 0.5]), vec!["-0.5", "0.0", "0.5"]);
+    }
+
+    #[test]
+    fn axis_scales() -> ChartResult<()> {
+        // log axes are ticked at powers of the base, with the multiples in between as minor ticks
+        let log = LogLocator { base: 10.0 };
+        let ticks = log.locate(3.0, 2000.0);
+        assert_eq!(ticks, vec![1.0, 10.0, 100.0, 1000.0, 10000.0]);
+        assert_eq!(log.minor(3.0, 2000.0)[..3], [2.0, 3.0, 4.0]);
+        assert_eq!(PowerFormatter { base: 10.0 }.format(&ticks), vec!["10⁰", "10¹", "10²", "10³", "10⁴"]);
+        assert_eq!(SymLogLocator { linthresh: 1.0 }.locate(-10.0, 100.0), vec![-10.0, -1.0, 0.0, 1.0, 10.0, 100.0]);
+
+        // each decade takes up the same space, and reversed scales run backwards
+        let scale = Scale::new(AxisScale::Log(10.0), (1.0, 1000.0), (0.0, 300.0));
+        assert!((scale.map(100.0) - 200.0).abs() < 1e-3);
+        assert!((scale.invert(100.0) - 10.0).abs() < 1e-3);
+        assert_eq!(Scale::new(AxisScale::Reversed, (0.0, 10.0), (0.0, 100.0)).map(2.0), 80.0);
+
+        // values a log scale can't show are left off rather than breaking the graph
+        let mut backend = SvgBackend::new(500, 500);
+        ScatterGraph::build()
+            .set_title("Latency")
+            .set_axis_text("Request", "Milliseconds")
+            .load_data(vec![(1.0, 0.0), (2.0, 15.0), (3.0, 2400.0)])
+            .set_y_scale(AxisScale::Log(10.0))
+            .draw_to(&mut backend)?;
+        let document = backend.document();
+        assert_eq!(document.matches("<circle").count(), 2);
+        assert!(document.contains("10³"));
+
+        // bars on a log scale start from the bottom of the axes, since it never reaches 0
+        let mut backend = SvgBackend::new(500, 500);
+        let graph = BarGraph::build()
+            .load_data(vec![("Reads", 20.0), ("Writes", 3000.0), ("Deletes", 0.0)])
+            .set_value_scale(AxisScale::Log(10.0));
+        assert_eq!(graph.dropped_points(), 1);
+        graph.draw_to(&mut backend)?;
+        let document = backend.document();
+        assert!(document.contains("10³"));
+        let bottoms: Vec<f32> = document
+            .lines()
+            .filter(|line| line.starts_with("<rect") && line.contains("fill=\"#1f77b4\""))
+            .map(|line| {
+                let number = |attribute: &str| line.split(attribute).nth(1).unwrap().split('"').next().unwrap().parse::<f32>().unwrap();
+                number(" y=\"") + number(" height=\"")
+            })
+            .collect();
+        assert_eq!(bottoms, vec![450.0, 450.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn categorical_axes() -> ChartResult<()> {
+        // where along the x-axis each label was written, from left to right
+        let label_order = |document: &str, labels: &[&str]| {
+            let mut found: Vec<(f32, String)> = document
+                .lines()
+                .filter_map(|line| {
+                    let label = labels.iter().find(|label| line.ends_with(&format!(">{label}</text>")))?;
+                    Some((line.split('"').nth(1)?.parse().ok()?, label.to_string()))
+                })
+                .collect();
+            found.sort_by(|a, b| a.0.total_cmp(&b.0));
+            found.into_iter().map(|(_, label)| label).collect::<Vec<_>>()
+        };
+        let services = ["search", "auth", "checkout"];
+
+        let mut backend = SvgBackend::new(500, 500);
+        ScatterGraph::build()
+            .add_category_series("", vec![("search", 45.0), ("auth", 30.0), ("search", 50.0)])
+            .add_category_series("", vec![("checkout", 120.0)])
+            .set_category_order(CategoryOrder::Sorted)
+            .draw_to(&mut backend)?;
+        let document = backend.document();
+        assert_eq!(label_order(&document, &services), ["auth", "checkout", "search"]);
+        // points in the same category share a column, in the middle of the category's band
+        let xs: Vec<&str> = document
+            .lines()
+            .filter(|line| line.starts_with("<circle"))
+            .map(|line| line.split('"').nth(1).unwrap())
+            .collect();
+        assert_eq!(xs[0], xs[2]);
+        assert_eq!(xs.len(), 4);
+
+        // listed categories come first, and the rest keep the order they were loaded in
+        let mut backend = SvgBackend::new(500, 500);
+        BarGraph::build()
+            .load_data(vec![("search", 45.0), ("auth", 30.0), ("checkout", 120.0)])
+            .set_category_order(CategoryOrder::custom(["checkout"]))
+            .draw_to(&mut backend)?;
+        assert_eq!(label_order(&backend.document(), &services), ["checkout", "search", "auth"]);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn time_axes() -> ChartResult<()> {
+        use chrono::{FixedOffset, TimeZone, Utc};
+
+        use crate::{charts::time::{TimeFormatter, TimeLocator}, data::Numeric};
+
+        // a day and a half is ticked every 6 hours, on the hour in the axis' timezone
+        let india = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        let start = india.with_ymd_and_hms(2024, 3, 1, 1, 20, 0).unwrap().to_f64();
+        let ticks = TimeLocator::new(india).locate(start, start + 36.0 * 3600.0);
+        assert_eq!(
+            TimeFormatter::new(india).format(&ticks),
+            vec!["Mar 1", "06:00", "12:00", "18:00", "Mar 2", "06:00", "12:00", "18:00"]
+        );
+
+        // months start on the 1st whatever their length, and are labelled with their year
+        let start = Utc.with_ymd_and_hms(2023, 11, 20, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 4, 3, 0, 0, 0).unwrap();
+        let ticks = TimeLocator::new(Utc).locate(start.to_f64(), end.to_f64());
+        assert_eq!(
+            TimeFormatter::new(Utc).format(&ticks),
+            vec!["Nov 2023", "Dec 2023", "Jan 2024", "Feb 2024", "Mar 2024", "Apr 2024", "May 2024"]
+        );
+        assert_eq!(TimeFormatter::with_format(Utc, "%Y-%m-%d").format(&ticks[..1]), vec!["2023-11-01"]);
+
+        let mut backend = SvgBackend::new(500, 500);
+        LineGraph::build()
+            .load_data(vec![(start, 1.0), (end, 2.0)])
+            .set_x_time_axis(Utc)
+            .draw_to(&mut backend)?;
+        assert!(backend.document().contains("Jan 2024"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn binning() {
+        assert_eq!(sturges(100), 8);
+
+        let edges = bin_edges(0.0, 4.0, 4);
+        assert_eq!(edges, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        // the last bin includes its upper edge
+        let counts = bin_counts(&[0.0, 0.5, 1.0, 3.5, 4.0, 5.0], &edges);
+        assert_eq!(counts, vec![2, 1, 0, 2]);
+
+        // there's nothing to work out from no samples at all
+        assert_eq!(quantile(&[3.0, 1.0, 2.0, 4.0], 0.5), Some(2.5));
+        assert_eq!(quantile(&[], 0.5), None);
+        assert_eq!(mean(&[]), None);
+        assert_eq!(freedman_diaconis(&[]), None);
+    }
+
     #[test]
     fn euc_distance() {
         // generate 100 random points and calculate distances
@@ -0,0 +1,58 @@
+//! Histogram binning related math functions and algorithms
+
+use super::statistics::{interquartile_range, standard_deviation};
+
+/// Calculate the number of bins to use with Sturges' rule. Formula being
+/// `k = ⌈log2(n)⌉ + 1`
+pub fn sturges(sample_count: usize) -> usize {
+    (sample_count.max(1) as f64).log2().ceil() as usize + 1
+}
+
+/// Calculate the width of each bin with Scott's normal reference rule. Formula being
+/// `h = 3.49σ / ∛n`
+/// Returns `None` if there are no samples.
+pub fn scott(samples: &[f64]) -> Option<f64> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    Some(3.49 * standard_deviation(samples) / (samples.len() as f64).cbrt())
+}
+
+/// Calculate the width of each bin with the Freedman–Diaconis rule. Formula being
+/// `h = 2 × IQR / ∛n`
+/// Returns `None` if there are no samples.
+pub fn freedman_diaconis(samples: &[f64]) -> Option<f64> {
+    Some(2.0 * interquartile_range(samples)? / (samples.len() as f64).cbrt())
+}
+
+/// Calculate `bin_count` evenly spaced bins covering `min..=max`.
+/// Returns the `bin_count + 1` edges of the bins.
+pub fn bin_edges(min: f64, max: f64, bin_count: usize) -> Vec<f64> {
+    let width = (max - min) / bin_count as f64;
+
+    (0..=bin_count).map(|i| min + width * i as f64).collect()
+}
+
+/// Count how many samples fall into each bin described by `edges`.
+/// Every bin includes its lower edge, and the last bin also includes its upper edge.
+/// Samples outside of the edges aren't counted.
+pub fn bin_counts(samples: &[f64], edges: &[f64]) -> Vec<usize> {
+    let bin_count = edges.len().saturating_sub(1);
+    let mut counts = vec![0; bin_count];
+    if bin_count == 0 {
+        return counts;
+    }
+
+    let (min, max) = (edges[0], edges[bin_count]);
+    for sample in samples {
+        if *sample < min || *sample > max {
+            continue;
+        }
+        // edges are sorted, so the bin is the number of upper edges the sample has reached
+        let bin = edges[1..].partition_point(|edge| edge <= sample).min(bin_count - 1);
+        counts[bin] += 1;
+    }
+
+    counts
+}
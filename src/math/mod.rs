@@ -1,2 +1,28 @@
 //! Module containing a range of mathematical functions and algorithms that relate to graphs.
+//!
+//! Data is worked with as `f64`s, so that large values such as timestamps keep their precision.
+//! Functions that also suit pixel positions, such as [`distance::euclidean_distance`], work with
+//! both `f32` and `f64` through [`Float`].
+use std::ops::{Add, Mul, Sub};
+
+pub mod binning;
 pub mod distance;
+pub mod statistics;
+
+/// A floating point type, either `f32` or `f64`.
+pub trait Float: Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    /// The square root of the number.
+    fn sqrt(self) -> Self;
+}
+
+impl Float for f32 {
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+}
+
+impl Float for f64 {
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+}
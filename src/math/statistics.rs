@@ -0,0 +1,46 @@
+//! Statistics related math functions and algorithms
+
+/// Calculate the mean (average) of a set of values. Formula being `μ = Σx / n`
+/// Returns `None` if there are no values.
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Calculate the sample standard deviation of a set of values. Formula being
+/// `s = √[ Σ(x – μ)^2 / (n – 1) ]`
+pub fn standard_deviation(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+
+    let Some(mean) = mean(values) else { return 0.0 };
+    let squared_differences: f64 = values.iter().map(|x| (x - mean).powi(2)).sum();
+
+    (squared_differences / (values.len() - 1) as f64).sqrt()
+}
+
+/// Calculate the `q`th quantile (`0.0..=1.0`) of a set of values,
+/// linearly interpolating between the two closest ranks. Returns `None` if there are no values.
+pub fn quantile(values: &[f64], q: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let rank = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64))
+}
+
+/// Calculate the interquartile range of a set of values. Formula being `IQR = Q3 – Q1`
+/// Returns `None` if there are no values.
+pub fn interquartile_range(values: &[f64]) -> Option<f64> {
+    Some(quantile(values, 0.75)? - quantile(values, 0.25)?)
+}